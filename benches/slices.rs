@@ -0,0 +1,84 @@
+//! Compares slice encoding against the previous approach of encoding every element into its
+//! own temporary `DataEncoder`, and `encoded_len` against measuring the encoded bytes.
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use dataparser_core::{
+    ByteSink, DataEncoder, Encodable, EncodingOptions, ParseResult, SinkEncodable,
+};
+
+struct Record {
+    id: u32,
+    name: String,
+    tags: Vec<u16>,
+}
+
+impl SinkEncodable for Record {
+    fn encode_to<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        sink.add_u32(self.id)?;
+        sink.add_string(self.name.as_str())?;
+        sink.add_slice(&self.tags)
+    }
+}
+
+fn records(n: usize) -> Vec<Record> {
+    (0..n)
+        .map(|i| Record {
+            id: i as u32,
+            name: format!("record-{i}"),
+            tags: (0..(i % 8) as u16).collect(),
+        })
+        .collect()
+}
+
+/// The previous slice layout, built with one temporary encoder (and options clone) per item.
+fn per_item_encoders(items: &[Record], options: &EncodingOptions) -> Vec<u8> {
+    let mut out = (items.len() as u32).to_be_bytes().to_vec();
+    for item in items {
+        let mut temp = DataEncoder::new();
+        temp.set_options(options);
+        item.encode_data(&mut temp).unwrap();
+        let built = temp.into_data();
+        out.extend((built.len() as u32).to_be_bytes());
+        out.extend(built);
+    }
+    out
+}
+
+fn slices(c: &mut Criterion) {
+    let options = EncodingOptions::default();
+    let mut group = c.benchmark_group("add_slice");
+    for n in [16, 256, 4096] {
+        let items = records(n);
+        assert_eq!(
+            per_item_encoders(&items, &options),
+            items.to_bytes(&options).unwrap()
+        );
+        group.bench_with_input(
+            BenchmarkId::new("per_item_encoders", n),
+            &items,
+            |b, items| b.iter(|| per_item_encoders(black_box(items), &options)),
+        );
+        group.bench_with_input(BenchmarkId::new("in_place", n), &items, |b, items| {
+            b.iter(|| {
+                let mut encoder = DataEncoder::new();
+                encoder.add_slice(black_box(items)).unwrap();
+                encoder.into_data()
+            })
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("encoded_len");
+    for n in [16, 256, 4096] {
+        let items = records(n);
+        group.bench_with_input(BenchmarkId::new("to_bytes", n), &items, |b, items| {
+            b.iter(|| black_box(items).to_bytes(&options).unwrap().len())
+        });
+        group.bench_with_input(BenchmarkId::new("counting_sink", n), &items, |b, items| {
+            b.iter(|| black_box(items).encoded_len(&options).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, slices);
+criterion_main!(benches);
@@ -0,0 +1,207 @@
+//! Length-delimited framing for [`tokio_util::codec`].
+//!
+//! [`DataCodec`] turns a byte stream into a stream of typed messages: every message is encoded
+//! into a frame preceded by its length, so a `Framed<TcpStream, DataCodec<T>>` reads and writes
+//! `T` values directly.
+//!
+//! ## Example
+//! ```rust
+//! # tokio::runtime::Runtime::new().unwrap().block_on(async {
+//! use dataparser_core::{codec::DataCodec, options::LengthPrefix};
+//! use futures::{SinkExt, StreamExt};
+//! use tokio_util::codec::Framed;
+//!
+//! let (client, server) = tokio::io::duplex(64);
+//! let codec = DataCodec::<String>::new().with_prefix(LengthPrefix::U16);
+//! let mut client = Framed::new(client, codec.clone());
+//! let mut server = Framed::new(server, codec);
+//!
+//! client.send("hello".to_string()).await?;
+//! client.send("world".to_string()).await?;
+//! assert_eq!(server.next().await.transpose()?, Some("hello".to_string()));
+//! assert_eq!(server.next().await.transpose()?, Some("world".to_string()));
+//! # Ok::<(), dataparser_core::DataParseError>(())
+//! # }).unwrap();
+//! ```
+use std::marker::PhantomData;
+
+use bytes::{Buf, BufMut, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+use crate::{
+    Decodable, Encodable,
+    errors::DataParseError,
+    options::{EncodingOptions, LengthPrefix, ParseOptions},
+    utils::ParseResult,
+};
+
+/// Default upper bound on the length of a frame, in bytes.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 8 * 1024 * 1024;
+
+/// A length-delimited codec for values that are [`Decodable`] and/or [`Encodable`].
+///
+/// Each frame is a length prefix (a `u32` by default, see [`with_prefix`]) followed by that
+/// many bytes holding exactly one encoded value. Frames longer than the maximum frame length
+/// are rejected on both ends with a [`DataParseError::FrameTooLong`] error, before any of
+/// their payload is buffered.
+///
+/// Values are decoded with the codec's [`ParseOptions`] and encoded with its
+/// [`EncodingOptions`]; the frame prefix uses the prefix endianness of those options.
+///
+/// # Example
+/// ```rust
+/// use bytes::BytesMut;
+/// use dataparser_core::{codec::DataCodec, options::LengthPrefix};
+/// use tokio_util::codec::{Decoder, Encoder};
+///
+/// let mut codec = DataCodec::<Vec<u8>>::new()
+///     .with_prefix(LengthPrefix::Varint)
+///     .with_max_frame_len(16);
+/// let mut buffer = BytesMut::new();
+/// codec.encode(vec![1, 2], &mut buffer)?;
+/// assert!(codec.encode(vec![0; 32], &mut buffer).is_err());
+///
+/// // Frames are only decoded once they have been fully received.
+/// let mut partial = buffer.split_to(5);
+/// assert_eq!(codec.decode(&mut partial)?, None);
+/// partial.unsplit(buffer);
+/// assert_eq!(codec.decode(&mut partial)?, Some(vec![1, 2]));
+/// assert!(partial.is_empty());
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+///
+/// [`with_prefix`]: DataCodec::with_prefix
+pub struct DataCodec<T> {
+    prefix: LengthPrefix,
+    max_frame_len: usize,
+    parse_options: ParseOptions,
+    encoding_options: EncodingOptions,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for DataCodec<T> {
+    fn default() -> Self {
+        Self {
+            prefix: LengthPrefix::default(),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+            parse_options: ParseOptions::default(),
+            encoding_options: EncodingOptions::default(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Clone for DataCodec<T> {
+    fn clone(&self) -> Self {
+        Self {
+            prefix: self.prefix,
+            max_frame_len: self.max_frame_len,
+            parse_options: self.parse_options.clone(),
+            encoding_options: self.encoding_options.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> DataCodec<T> {
+    /// Creates a codec with a `u32` frame prefix, a maximum frame length of
+    /// [`DEFAULT_MAX_FRAME_LEN`] and default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the kind of the frame length prefix and returns the updated codec (builder-style).
+    pub fn with_prefix(mut self, prefix: LengthPrefix) -> Self {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Sets the maximum frame length in bytes and returns the updated codec (builder-style).
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Sets the options used to decode frames and returns the updated codec (builder-style).
+    pub fn with_parse_options(mut self, options: ParseOptions) -> Self {
+        self.parse_options = options;
+        self
+    }
+
+    /// Sets the options used to encode frames and returns the updated codec (builder-style).
+    pub fn with_encoding_options(mut self, options: EncodingOptions) -> Self {
+        self.encoding_options = options;
+        self
+    }
+
+    /// Returns the maximum frame length in bytes.
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    fn check_frame_len(&self, len: usize) -> ParseResult<()> {
+        if len > self.max_frame_len {
+            return Err(DataParseError::FrameTooLong {
+                len,
+                max: self.max_frame_len,
+            });
+        }
+        Ok(())
+    }
+
+    /// Reads the frame prefix at the start of `src`, returning the frame length and the
+    /// size of the prefix, or `None` if the prefix itself is incomplete.
+    fn peek_frame_len(&self, src: &[u8]) -> ParseResult<Option<(usize, usize)>> {
+        let mut read = 0;
+        let len = self
+            .prefix
+            .decode(self.parse_options.prefix_endianness(), || {
+                let byte = src.get(read).copied().ok_or(DataParseError::Incomplete {
+                    needed: read + 1,
+                    available: src.len(),
+                })?;
+                read += 1;
+                Ok(byte)
+            });
+        match len {
+            Ok(len) => Ok(Some((len, read))),
+            Err(e) if e.is_incomplete() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl<T: Decodable> Decoder for DataCodec<T> {
+    type Item = T;
+    type Error = DataParseError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> ParseResult<Option<T>> {
+        let Some((len, header)) = self.peek_frame_len(src)? else {
+            return Ok(None);
+        };
+        self.check_frame_len(len)?;
+        if src.len() < header + len {
+            src.reserve(header + len - src.len());
+            return Ok(None);
+        }
+        src.advance(header);
+        let frame = src.split_to(len);
+        T::from_bytes(&frame, &self.parse_options).map(Some)
+    }
+}
+
+impl<T: Encodable> Encoder<T> for DataCodec<T> {
+    type Error = DataParseError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> ParseResult<()> {
+        let frame = item.to_bytes(&self.encoding_options)?;
+        self.check_frame_len(frame.len())?;
+        let prefix = self
+            .encoding_options
+            .encode_length(frame.len(), self.prefix)?;
+        dst.reserve(prefix.len() + frame.len());
+        dst.put_slice(&prefix);
+        dst.put_slice(&frame);
+        Ok(())
+    }
+}
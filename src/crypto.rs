@@ -81,14 +81,20 @@ impl DataParser<'_> {
 #[cfg(feature = "crypto")]
 impl DataEncoder {
     /// Encrypts the encoder's internal writer buffer using AES-256-CBC.
+    ///
+    /// Placeholders reserved before this call can no longer be filled.
     pub fn encrypt(&mut self) -> ParseResult<()> {
         aes_encrypt(&mut self.buffer, &self.options.key, &self.options.iv)?;
+        self.rollbacks.push(0);
         Ok(())
     }
 
     /// Decrypts the encoder's internal writer buffer using AES-256-CBC.
+    ///
+    /// Placeholders reserved before this call can no longer be filled.
     pub fn decrypt(&mut self) -> ParseResult<()> {
         aes_decrypt(&mut self.buffer, &self.options.key, &self.options.iv)?;
+        self.rollbacks.push(0);
         Ok(())
     }
 }
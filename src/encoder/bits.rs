@@ -0,0 +1,112 @@
+use crate::encoder::core::DataEncoder;
+use crate::errors::DataParseError;
+use crate::utils::{BitOrder, ParseResult};
+
+/// A bit-granular writer over a [`DataEncoder`].
+///
+/// Bits are accumulated into a pending byte which is appended to the encoder once full.
+/// A partially filled byte is zero-padded and written by [`align`] or [`finish`]; bits
+/// still pending when the writer is dropped without either call are lost.
+///
+/// Bytes are appended directly and are not affected by `prepend_data_size`.
+///
+/// # Example
+/// ```rust
+/// use dataparser_core::{DataEncoder, utils::BitOrder};
+/// let mut encoder = DataEncoder::new();
+/// let mut bits = encoder.bits(BitOrder::MsbFirst);
+/// bits.write_bits(4, 4).unwrap();
+/// bits.write_bits(5, 4).unwrap();
+/// bits.write_bit(true).unwrap();
+/// let encoder = bits.finish();
+/// encoder.add_u8(0xFF).unwrap();
+/// assert_eq!(encoder.get_data().unwrap(), &vec![0x45, 0x80, 0xFF]);
+/// ```
+///
+/// [`align`]: BitWriter::align
+/// [`finish`]: BitWriter::finish
+pub struct BitWriter<'e> {
+    encoder: &'e mut DataEncoder,
+    order: BitOrder,
+    current: u8,
+    bits_used: u32,
+}
+
+impl<'e> BitWriter<'e> {
+    /// Creates a bit writer that appends to `encoder`.
+    pub fn new(encoder: &'e mut DataEncoder, order: BitOrder) -> Self {
+        Self {
+            encoder,
+            order,
+            current: 0,
+            bits_used: 0,
+        }
+    }
+
+    /// Writes the low `n` bits (at most 64) of `value`.
+    ///
+    /// # Errors
+    /// Returns an error if `n` is greater than 64.
+    pub fn write_bits(&mut self, value: u64, n: u32) -> ParseResult<()> {
+        if n > u64::BITS {
+            return Err(DataParseError::Custom {
+                e: format!("Cannot write {} bits from a u64", n),
+            });
+        }
+        let mut written = 0;
+        while written < n {
+            let take = (n - written).min(8 - self.bits_used);
+            let mask = (1u64 << take) - 1;
+            match self.order {
+                BitOrder::MsbFirst => {
+                    let chunk = (value >> (n - written - take)) & mask;
+                    self.current |= (chunk as u8) << (8 - self.bits_used - take);
+                }
+                BitOrder::LsbFirst => {
+                    let chunk = (value >> written) & mask;
+                    self.current |= (chunk as u8) << self.bits_used;
+                }
+            }
+            self.bits_used += take;
+            written += take;
+            if self.bits_used == 8 {
+                self.align();
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> ParseResult<()> {
+        self.write_bits(bit as u64, 1)
+    }
+
+    /// Returns `true` if no bits are pending in a partially filled byte.
+    pub fn is_aligned(&self) -> bool {
+        self.bits_used == 0
+    }
+
+    /// Pads the pending byte with zero bits and writes it, so the next write starts on a byte boundary.
+    pub fn align(&mut self) {
+        if self.bits_used > 0 {
+            self.encoder.buffer.push(self.current);
+            self.current = 0;
+            self.bits_used = 0;
+        }
+    }
+
+    /// Re-aligns to the next byte boundary and hands the underlying encoder back.
+    pub fn finish(mut self) -> &'e mut DataEncoder {
+        self.align();
+        self.encoder
+    }
+}
+
+impl DataEncoder {
+    /// Starts writing at bit granularity.
+    ///
+    /// See [`BitWriter`] for details.
+    pub fn bits(&mut self, order: BitOrder) -> BitWriter<'_> {
+        BitWriter::new(self, order)
+    }
+}
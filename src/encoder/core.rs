@@ -16,8 +16,8 @@
 //!
 //! ## Example
 //! ```rust
-//! use libparser::encoder::DataEncoder;
-//! use libparser::Encodable;
+//! use dataparser_core::encoder::core::DataEncoder;
+//! use dataparser_core::Encodable;
 //!
 //! #[derive(Debug)]
 //! struct Header {
@@ -26,7 +26,7 @@
 //! }
 //!
 //! impl Encodable for Header {
-//!     fn encode_data(&self, encoder: &mut DataEncoder) -> Result<(), libparser::errors::DataParseError> {
+//!     fn encode_data(&self, encoder: &mut DataEncoder) -> Result<(), dataparser_core::errors::DataParseError> {
 //!         encoder.add_u32(self.id)?;
 //!         encoder.add_bool(self.flag)?;
 //!         Ok(())
@@ -37,7 +37,8 @@
 //! let header = Header { id: 42, flag: true };
 //! header.encode_data(&mut encoder)?;
 //! let bytes = encoder.get_data()?;
-//! assert_eq!(bytes, vec![0x00, 0x00, 0x00, 0x2A, 0x01]);
+//! assert_eq!(bytes, &vec![0x00, 0x00, 0x00, 0x2A, 0x01]);
+//! # Ok::<(), dataparser_core::DataParseError>(())
 //! ```
 //!
 //! ## Features
@@ -47,23 +48,57 @@
 //! [`EncodingOptions`]: crate::options::EncodingOptions
 //! [`Encodable`]: crate::Encodable
 //! [`EndianSerialize`]: crate::utils::EndianSerialize
-use crate::{
-    Encodable, impl_number,
-    options::EncodingOptions,
-    utils::{EndianSerialize, ParseResult},
-};
-#[derive(Default)]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{impl_sink_methods, options::EncodingOptions, utils::ParseResult};
+
+/// Source of the ids that tie placeholders to the encoder that reserved them.
+static NEXT_ENCODER_ID: AtomicU64 = AtomicU64::new(0);
+
 pub struct DataEncoder {
     pub(crate) buffer: Vec<u8>,
     pub(crate) options: EncodingOptions,
+
+    /// Identifies this encoder to the placeholders it hands out.
+    pub(crate) id: u64,
+
+    /// Lengths the buffer was cut back to, oldest first. A placeholder reserved before one
+    /// of them that ends past it no longer points at its field.
+    pub(crate) rollbacks: Vec<usize>,
+}
+
+impl Default for DataEncoder {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+/// A clone is a separate encoder: placeholders reserved in one can't fill the other.
+impl Clone for DataEncoder {
+    fn clone(&self) -> Self {
+        Self {
+            buffer: self.buffer.clone(),
+            options: self.options.clone(),
+            id: NEXT_ENCODER_ID.fetch_add(1, Ordering::Relaxed),
+            rollbacks: self.rollbacks.clone(),
+        }
+    }
 }
 
 impl DataEncoder {
     /// Creates a new encoder with default options.
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new encoder with default options and room for `capacity` bytes, e.g. the
+    /// [`encoded_len`](Encodable::encoded_len) of the values about to be encoded.
+    pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            buffer: Vec::new(),
+            buffer: Vec::with_capacity(capacity),
             options: EncodingOptions::default(),
+            id: NEXT_ENCODER_ID.fetch_add(1, Ordering::Relaxed),
+            rollbacks: Vec::new(),
         }
     }
 
@@ -76,66 +111,17 @@ impl DataEncoder {
         self.options = options.clone(); //TODO: figure out a way to remove the clone usage
     }
 
-    /// Adds a raw byte slice (or any `AsRef<[u8]>`) to the buffer.
-    ///
-    /// If `options.prepend_data_size` is `true`, a `u32` length prefix (in the selected endianness)
-    /// is prepended to the data.
-    ///
-    /// # Errors
-    /// Returns an error if the internal logic fails (always returns `Ok(())` currently).
-    pub(crate) fn add_item<T>(&mut self, data: T) -> ParseResult<()>
-    where
-        T: AsRef<[u8]>,
-    {
-        let data = data.as_ref();
-        if self.options.prepend_data_size {
-            let data_len = data.len() as u32;
-            self.buffer
-                .extend_from_slice(&data_len.to_endian_bytes(&self.options.endianness));
-        }
-        self.buffer.extend_from_slice(data);
-        Ok(())
-    }
-
-    /// Adds a number (integer or float) using the configured endianness.
-    ///
-    /// Internally calls `to_endian_bytes()` and delegates to `add_item`.
-    fn add_num<T: EndianSerialize>(&mut self, n: T) -> ParseResult<()> {
-        let data = n.to_endian_bytes(&self.options.endianness);
-        self.add_item(data)
-    }
-
-    /// Serializes a slice of encodable items.
-    ///
-    /// The format is:
-    /// - A `u32` indicating the number of items
-    /// - For each item:
-    ///     - A `u32` length prefix
-    ///     - The item's serialized bytes
-    ///
-    /// Internally creates a temporary encoder for each item to isolate its byte representation.
-    ///
-    /// # Note
-    /// Each item is encoded in isolation with the same encoding options.
-    /// This allows complex or nested data to be safely serialized.
-    pub fn add_slice<T: Encodable>(&mut self, data: &[T]) -> ParseResult<()> {
-        let data_len = data.len();
-        self.add_u32(data_len as u32)?;
-        for item in data {
-            let mut temp_encoder = DataEncoder::default();
-            temp_encoder.set_options(&self.options);
-            item.encode_data(&mut temp_encoder)?;
-            let built = temp_encoder.get_data()?;
-            self.add_u32(built.len() as u32)?;
-            self.add_item(built)?;
-        }
-        Ok(())
-    }
-
     /// Returns a copy of the encoder’s internal buffer.
     pub fn get_data(&self) -> ParseResult<&Vec<u8>> {
         Ok(&self.buffer)
     }
 
-    impl_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+    /// Consumes the encoder and returns its buffer.
+    pub fn into_data(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    // `add_*` methods for every value the encoder can write; the layout of each one is
+    // defined by `ByteSink`.
+    impl_sink_methods!();
 }
@@ -0,0 +1,59 @@
+use crate::{encoder::sink::ByteSink, options::EncodingOptions, utils::ParseResult};
+
+/// A [`ByteSink`] that only counts the bytes written to it.
+///
+/// Encoding a value into a `CountingSink` yields the exact length it would have when
+/// encoded with a [`DataEncoder`] using the same options, including any error, without
+/// building the output. This is how [`Encodable::encoded_len`] is computed for
+/// [`SinkEncodable`] types.
+///
+/// # Example
+/// ```rust
+/// use dataparser_core::{ByteSink, EncodingOptions, encoder::counting::CountingSink};
+/// let mut sink = CountingSink::new(EncodingOptions::default());
+/// sink.add_u16(7)?;
+/// sink.add_string("abc")?;
+/// assert_eq!(sink.len(), 2 + 4 + 3);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+///
+/// [`DataEncoder`]: crate::encoder::core::DataEncoder
+/// [`Encodable::encoded_len`]: crate::Encodable::encoded_len
+/// [`SinkEncodable`]: crate::SinkEncodable
+#[derive(Default, Clone)]
+pub struct CountingSink {
+    pub(crate) options: EncodingOptions,
+    len: usize,
+}
+
+impl CountingSink {
+    /// Creates a sink that counts bytes as they would be encoded with `options`.
+    pub fn new(options: EncodingOptions) -> Self {
+        Self { options, len: 0 }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been written yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl ByteSink for CountingSink {
+    fn options(&self) -> &EncodingOptions {
+        &self.options
+    }
+
+    fn put_bytes(&mut self, data: &[u8]) -> ParseResult<()> {
+        self.len += data.len();
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
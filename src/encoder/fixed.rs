@@ -0,0 +1,174 @@
+use crate::{
+    Encodable, encoder::sink::ByteSink, errors::DataParseError, impl_sink_methods,
+    options::EncodingOptions, utils::ParseResult,
+};
+
+/// An encoder that writes in place into a fixed-size, caller-provided buffer.
+///
+/// `FixedEncoder` has the same `add_*` methods as [`DataEncoder`] and produces the same
+/// bytes, but never grows its buffer: a write that doesn't fit fails with
+/// [`DataParseError::BufferFull`] and leaves the encoder where it was. Fixed-width numbers
+/// and booleans are written without allocating.
+///
+/// Through [`ByteSink`] (e.g. inside [`SinkEncodable::encode_to`]), a failed write may
+/// leave part of a value written; [`encode`](FixedEncoder::encode) rolls the whole value
+/// back.
+///
+/// # Example
+/// ```rust
+/// use dataparser_core::{DataParseError, encoder::fixed::FixedEncoder};
+/// let mut scratch = [0u8; 8];
+/// let mut encoder = FixedEncoder::new(&mut scratch[..]);
+/// encoder.add_u16(0x0102)?;
+/// encoder.add_string("ab")?;
+/// assert_eq!(encoder.get_data(), &[0x01, 0x02, 0x00, 0x00, 0x00, 0x02, b'a', b'b']);
+///
+/// match encoder.add_u32(7) {
+///     Err(DataParseError::BufferFull { required, capacity }) => assert_eq!((required, capacity), (12, 8)),
+///     other => panic!("unexpected result: {other:?}"),
+/// }
+/// assert_eq!(encoder.position(), 8);
+///
+/// // The bytes were written straight into `scratch`.
+/// assert_eq!(scratch, [0x01, 0x02, 0x00, 0x00, 0x00, 0x02, b'a', b'b']);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+///
+/// [`DataEncoder`]: crate::encoder::core::DataEncoder
+/// [`SinkEncodable::encode_to`]: crate::SinkEncodable::encode_to
+pub struct FixedEncoder<'a> {
+    pub(crate) buffer: &'a mut [u8],
+    pub(crate) options: EncodingOptions,
+    position: usize,
+}
+
+impl<'a> FixedEncoder<'a> {
+    /// Creates an encoder with default options that writes to the start of `buffer`.
+    pub fn new(buffer: &'a mut [u8]) -> Self {
+        Self::with_options(buffer, EncodingOptions::default())
+    }
+
+    /// Creates an encoder with the given options that writes to the start of `buffer`.
+    pub fn with_options(buffer: &'a mut [u8], options: EncodingOptions) -> Self {
+        Self {
+            buffer,
+            options,
+            position: 0,
+        }
+    }
+
+    /// Sets the encoding options for this encoder.
+    pub fn set_options(&mut self, options: EncodingOptions) {
+        self.options = options;
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the size of the underlying buffer.
+    pub fn capacity(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Returns the number of bytes that can still be written.
+    pub fn remaining(&self) -> usize {
+        self.capacity() - self.position
+    }
+
+    /// Returns the bytes written so far.
+    pub fn get_data(&self) -> &[u8] {
+        &self.buffer[..self.position]
+    }
+
+    /// Discards everything written so far, so the buffer can be reused.
+    pub fn clear(&mut self) {
+        self.position = 0;
+    }
+
+    /// Consumes the encoder and returns its buffer.
+    pub fn into_buffer(self) -> &'a mut [u8] {
+        self.buffer
+    }
+
+    /// Encodes `value` after the bytes written so far.
+    ///
+    /// The value is measured with [`Encodable::encoded_len`] first, so a value that doesn't
+    /// fit is rejected before any byte is written. [`SinkEncodable`] types are then written
+    /// in place; other types are encoded into a temporary buffer first (see
+    /// [`Encodable::encode_into`]).
+    ///
+    /// # Errors
+    /// Returns [`DataParseError::BufferFull`] with the size the buffer needs to hold the
+    /// whole value if it doesn't fit, in which case the buffer is left untouched. If encoding
+    /// fails for another reason, the encoder is rewound to where it was.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{DataParseError, Encodable, EncodingOptions, encoder::fixed::FixedEncoder};
+    /// let value = vec!["hello".to_string(), "world".to_string()];
+    /// let mut scratch = [0u8; 16];
+    /// let mut encoder = FixedEncoder::new(&mut scratch[..]);
+    /// match encoder.encode(&value) {
+    ///     Err(DataParseError::BufferFull { required, .. }) => {
+    ///         assert_eq!(required, value.encoded_len(&EncodingOptions::default())?);
+    ///     }
+    ///     other => panic!("unexpected result: {other:?}"),
+    /// }
+    /// assert_eq!(encoder.position(), 0);
+    ///
+    /// let mut scratch = [0u8; 64];
+    /// let mut encoder = FixedEncoder::new(&mut scratch[..]);
+    /// encoder.encode(&value)?;
+    /// assert_eq!(encoder.get_data(), value.to_bytes(&EncodingOptions::default())?);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    ///
+    /// [`SinkEncodable`]: crate::SinkEncodable
+    pub fn encode<T: Encodable>(&mut self, value: &T) -> ParseResult<()> {
+        let len = value.encoded_len(&self.options)?;
+        self.check_room(len)?;
+        self.atomic(|enc| value.encode_into(enc))
+    }
+
+    /// Checks that `len` more bytes fit in the buffer.
+    fn check_room(&self, len: usize) -> ParseResult<()> {
+        let capacity = self.capacity();
+        match self.position.checked_add(len) {
+            Some(required) if required <= capacity => Ok(()),
+            required => Err(DataParseError::BufferFull {
+                required: required.unwrap_or(usize::MAX),
+                capacity,
+            }),
+        }
+    }
+
+    /// Runs `f`, rewinding to the current position if it fails.
+    fn atomic<T>(&mut self, f: impl FnOnce(&mut Self) -> ParseResult<T>) -> ParseResult<T> {
+        let start = self.position;
+        f(self).inspect_err(|_| self.position = start)
+    }
+
+    // `add_*` methods for every value the encoder can write; the layout of each one is
+    // defined by `ByteSink`. A call that fails is rolled back.
+    impl_sink_methods!(atomic);
+}
+
+impl ByteSink for FixedEncoder<'_> {
+    fn options(&self) -> &EncodingOptions {
+        &self.options
+    }
+
+    fn put_bytes(&mut self, data: &[u8]) -> ParseResult<()> {
+        self.check_room(data.len())?;
+        let end = self.position + data.len();
+        self.buffer[self.position..end].copy_from_slice(data);
+        self.position = end;
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.position
+    }
+}
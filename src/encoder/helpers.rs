@@ -1,30 +1,32 @@
 use super::core::DataEncoder;
-use crate::impl_encodable;
+use super::counting::CountingSink;
+use super::sink::ByteSink;
+use crate::options::EncodingOptions;
 use crate::utils::ParseResult;
 
 impl DataEncoder {
-    /// Adds a string value to the encoder.
+    /// Writes `tag` followed by whatever `payload` writes.
     ///
-    /// This method converts the input into a `String`, writes its length (`u32`),
-    /// then writes the raw bytes.
+    /// This is the encoding counterpart of the [`dispatch`] combinator: the tag selects
+    /// which payload layout a decoder should expect.
     ///
     /// # Example
-    /// ```rust
-    /// encoder.add_string("hello")?;
+    /// ```
+    /// use dataparser_core::DataEncoder;
+    /// let mut encoder = DataEncoder::new();
+    /// encoder.add_tagged(2u8, |e| e.add_u16(0x0102))?;
+    /// assert_eq!(encoder.get_data()?, &vec![0x02, 0x01, 0x02]);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
     /// ```
     ///
-    /// This is equivalent to calling `String::encode_data(...)` directly.
-    pub fn add_string(&mut self, data: impl Into<String>) -> ParseResult<()> {
-        let data: String = data.into();
-        self.add_u32(data.len() as u32)?;
-        self.add_item(data)
-    }
-
-    /// Adds a single boolean value to the encoder.
-    ///
-    /// Encoded as a single byte: `0x01` for `true`, `0x00` for `false`.
-    pub fn add_bool(&mut self, data: bool) -> ParseResult<()> {
-        self.add_item(vec![data as u8])
+    /// [`dispatch`]: crate::parser::combinators::dispatch
+    pub fn add_tagged<T, F>(&mut self, tag: T, payload: F) -> ParseResult<()>
+    where
+        T: Encodable,
+        F: FnOnce(&mut DataEncoder) -> ParseResult<()>,
+    {
+        tag.encode_data(self)?;
+        payload(self)
     }
 }
 
@@ -36,6 +38,8 @@ impl DataEncoder {
 ///
 /// # Example
 /// ```rust
+/// use dataparser_core::{DataEncoder, Encodable, ParseResult};
+///
 /// struct MyData {
 ///     id: u32,
 ///     flag: bool,
@@ -43,7 +47,7 @@ impl DataEncoder {
 ///
 /// impl Encodable for MyData {
 ///     fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
-///         encoder.add_item(self.id)?;
+///         encoder.add_u32(self.id)?;
 ///         encoder.add_bool(self.flag)
 ///     }
 /// }
@@ -52,41 +56,99 @@ impl DataEncoder {
 /// [`DataEncoder`]: crate::encoder::core::DataEncoder
 pub trait Encodable {
     fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()>;
+
+    /// Encodes the value into a new byte vector.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{Encodable, EncodingOptions};
+    /// assert_eq!(0x0102u16.to_bytes(&EncodingOptions::default())?, vec![0x01, 0x02]);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    fn to_bytes(&self, options: &EncodingOptions) -> ParseResult<Vec<u8>> {
+        let mut encoder = DataEncoder::new();
+        encoder.set_options(options);
+        self.encode_data(&mut encoder)?;
+        Ok(encoder.into_data())
+    }
+
+    /// Returns the number of bytes the value encodes to with the given options.
+    ///
+    /// This encodes the value into a [`CountingSink`] with [`encode_into`], which measures
+    /// [`SinkEncodable`] types and containers without building the output. For types
+    /// implementing `Encodable` directly, `encode_into` encodes the value into a temporary
+    /// buffer, so they may want to override this.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{Encodable, EncodingOptions};
+    /// let value = (vec!["ab".to_string()], 7u16);
+    /// let options = EncodingOptions::default();
+    /// assert_eq!(value.0.encoded_len(&options)?, value.0.to_bytes(&options)?.len());
+    /// assert_eq!(value.1.encoded_len(&options)?, 2);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    ///
+    /// [`encode_into`]: Encodable::encode_into
+    /// [`SinkEncodable`]: crate::SinkEncodable
+    /// [`CountingSink`]: crate::encoder::counting::CountingSink
+    fn encoded_len(&self, options: &EncodingOptions) -> ParseResult<usize> {
+        let mut sink = CountingSink::new(options.clone());
+        self.encode_into(&mut sink)?;
+        Ok(sink.len())
+    }
+
+    /// Encodes the value into any [`ByteSink`], with the sink's options.
+    ///
+    /// [`SinkEncodable`] types and containers write straight into the sink; the default
+    /// implementation encodes the value with [`to_bytes`](Encodable::to_bytes) and writes
+    /// the result.
+    ///
+    /// [`SinkEncodable`]: crate::SinkEncodable
+    fn encode_into<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        let bytes = self.to_bytes(sink.options())?;
+        sink.put_bytes(&bytes)
+    }
 }
 
-/// Implements `Encodable` for `Option<T>` by writing a boolean flag followed by the value (if present).
+/// Encodes `Option<T>` as a boolean flag followed by the value (if present).
 ///
 /// Format:
 /// - `0x01` followed by encoded `T` if `Some`
 /// - `0x00` if `None`
 impl<T: Encodable> Encodable for Option<T> {
-    fn encode_data(&self, writer: &mut DataEncoder) -> ParseResult<()> {
+    fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
         match self {
             Some(value) => {
-                writer.add_bool(true)?;
-                value.encode_data(writer)
-            }
-            None => {
-                writer.add_bool(false)?;
-                Ok(())
+                encoder.add_bool(true)?;
+                value.encode_data(encoder)
             }
+            None => encoder.add_bool(false),
         }
     }
+
+    fn encode_into<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        sink.add_option(self.as_ref())
+    }
 }
 
-/// Implements `Encodable` for `Vec<T>` by prefixing the length (as `u32`), and encoding each element.
+/// Encodes `Vec<T>` as an element count followed by each element in a length-prefixed section.
 ///
 /// Format:
-/// - `[length: u32][item1][item2]...[itemN]`
+/// - `[count: prefix][len1: prefix][item1]...[lenN: prefix][itemN]`
 ///
 /// Note: Internally uses `add_slice`.
 impl<T: Encodable> Encodable for Vec<T> {
     fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
         encoder.add_slice(self)
     }
+
+    fn encode_into<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        sink.add_slice(self)
+    }
 }
 
-/// Implements `Encodable` for arrays `[T; N]` by encoding each element sequentially.
+/// Encodes arrays `[T; N]` by encoding each element sequentially.
 ///
 /// Does **not** include a length prefix; assumes caller knows the array size.
 ///
@@ -99,13 +161,11 @@ impl<T: Encodable, const N: usize> Encodable for [T; N] {
         }
         Ok(())
     }
-}
 
-impl Encodable for String {
-    fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
-        encoder.add_u32(self.len() as u32)?;
-        encoder.add_item(self.as_bytes())
+    fn encode_into<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        for item in self {
+            item.encode_into(sink)?;
+        }
+        Ok(())
     }
 }
-
-impl_encodable!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
@@ -9,19 +9,45 @@ macro_rules! impl_endian_serialize {
                         $crate::utils::Endianness::NativeEndian => self.to_ne_bytes().to_vec(),
                     }
                 }
+
+                fn write_endian_bytes(self, endian: &$crate::utils::Endianness, out: &mut [u8]) {
+                    match endian {
+                        $crate::utils::Endianness::BigEndian => out.copy_from_slice(&self.to_be_bytes()),
+                        $crate::utils::Endianness::LittleEndian => out.copy_from_slice(&self.to_le_bytes()),
+                        $crate::utils::Endianness::NativeEndian => out.copy_from_slice(&self.to_ne_bytes()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_endian_serialize!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
+#[macro_export]
+macro_rules! impl_encodable {
+    ($($t:ty),* $(,)?) => {
+        $(
+        paste::paste! {
+            impl $crate::Encodable for $t {
+                fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
+                    encoder.[<add_ $t>](*self)
+                }
             }
+        }
         )*
     };
 }
 
-impl_endian_serialize!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
 #[macro_export]
-macro_rules! impl_number {
+macro_rules! impl_reserve {
     ($($t:ty),* $(,)?) => {
         $(
             paste::paste! {
-                pub fn [<add_ $t>](&mut self, n: $t) -> $crate::utils::ParseResult<()> {
-                    self.add_num(n)
+                #[doc = "Writes a zeroed `" $t "` in the configured byte order and returns a handle to [`fill`](Self::fill) it in later."]
+                pub fn [<reserve_ $t>](&mut self) -> $crate::utils::ParseResult<$crate::encoder::placeholder::Placeholder<$t>> {
+                    self.reserve(self.options.endianness.clone())
                 }
             }
         )*
@@ -29,16 +55,165 @@ macro_rules! impl_number {
 }
 
 #[macro_export]
-macro_rules! impl_encodable {
+macro_rules! impl_sink_encodable {
     ($($t:ty),* $(,)?) => {
         $(
         paste::paste! {
-            impl $crate::Encodable for $t {
-                fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
-                    encoder.[<add_ $t>](*self)
+            impl $crate::encoder::sink::SinkEncodable for $t {
+                fn encode_to<S: $crate::encoder::sink::ByteSink>(&self, sink: &mut S) -> $crate::utils::ParseResult<()> {
+                    sink.[<add_ $t>](*self)
                 }
             }
         }
         )*
     };
 }
+
+/// Provides the `add_*` methods of [`ByteSink`](crate::encoder::sink::ByteSink) for
+/// fixed-width numbers and varints, on top of `add_item`.
+#[macro_export]
+macro_rules! impl_sink_defaults {
+    ($($t:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                #[doc = "Writes a `" $t "` in the configured byte order."]
+                fn [<add_ $t>](&mut self, n: $t) -> $crate::utils::ParseResult<()> {
+                    let mut data = [0u8; size_of::<$t>()];
+                    $crate::utils::EndianSerialize::write_endian_bytes(
+                        n,
+                        &self.options().endianness,
+                        &mut data,
+                    );
+                    self.add_item(&data)
+                }
+            }
+        )*
+    };
+    (@unsigned $($t:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                fn [<add_leb128_ $t>](&mut self, n: $t) -> $crate::utils::ParseResult<()> {
+                    self.add_item(&$crate::utils::encode_uleb128(n as u128))
+                }
+            }
+        )*
+    };
+    (@signed $($t:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                fn [<add_leb128_ $t>](&mut self, n: $t) -> $crate::utils::ParseResult<()> {
+                    self.add_item(&$crate::utils::encode_sleb128(n as i128))
+                }
+                fn [<add_zigzag_ $t>](&mut self, n: $t) -> $crate::utils::ParseResult<()> {
+                    self.add_item(&$crate::utils::encode_uleb128($crate::utils::zigzag_encode(n as i128)))
+                }
+            }
+        )*
+    };
+}
+
+/// Adds inherent `add_*` methods that forward to the [`ByteSink`](crate::encoder::sink::ByteSink)
+/// methods of the same name, so a sink can be used without importing the trait.
+///
+/// With `atomic`, each call goes through the type's `atomic` method, which rewinds whatever
+/// a failed call wrote. With `async`, the methods are `async`: the value is staged the same
+/// way, then sent with `write_pending`. Slices aren't forwarded in `async` mode.
+#[macro_export]
+macro_rules! impl_sink_methods {
+    () => {
+        $crate::impl_sink_methods!(@methods () self);
+        $crate::impl_sink_methods!(@slices () self);
+    };
+    (atomic) => {
+        $crate::impl_sink_methods!(@methods (atomic) self);
+        $crate::impl_sink_methods!(@slices (atomic) self);
+    };
+    (async) => {
+        $crate::impl_sink_methods!(@methods (async) self);
+    };
+    (@call () $self:tt $name:ident($($arg:expr),*)) => {
+        <Self as $crate::encoder::sink::ByteSink>::$name($self, $($arg),*)
+    };
+    (@call (atomic) $self:tt $name:ident($($arg:expr),*)) => {
+        $self.atomic(|sink| <Self as $crate::encoder::sink::ByteSink>::$name(sink, $($arg),*))
+    };
+    (@call (async) $self:tt $name:ident($($arg:expr),*)) => {{
+        $self.atomic(|sink| <Self as $crate::encoder::sink::ByteSink>::$name(sink, $($arg),*))?;
+        $self.write_pending().await
+    }};
+    (@fn (async) $self:tt $name:ident($($param:ident: $ty:ty),*) => ($($arg:expr),*)) => {
+        #[doc = concat!("See [`ByteSink::", stringify!($name), "`](crate::ByteSink::", stringify!($name), ").")]
+        pub async fn $name(&mut $self, $($param: $ty),*) -> $crate::utils::ParseResult<()> {
+            $crate::impl_sink_methods!(@call (async) $self $name($($arg),*))
+        }
+    };
+    (@fn $mode:tt $self:tt $name:ident($($param:ident: $ty:ty),*) => ($($arg:expr),*)) => {
+        #[doc = concat!("See [`ByteSink::", stringify!($name), "`](crate::ByteSink::", stringify!($name), ").")]
+        pub fn $name(&mut $self, $($param: $ty),*) -> $crate::utils::ParseResult<()> {
+            $crate::impl_sink_methods!(@call $mode $self $name($($arg),*))
+        }
+    };
+    (@methods $mode:tt $self:tt) => {
+        $crate::impl_sink_methods!(@fn $mode $self
+            add_length(len: usize, prefix: $crate::options::LengthPrefix) => (len, prefix));
+        $crate::impl_sink_methods!(@fn $mode $self add_bool(data: bool) => (data));
+        $crate::impl_sink_methods!(@fn $mode $self add_uint(n: u64, n_bytes: usize) => (n, n_bytes));
+        $crate::impl_sink_methods!(@fn $mode $self add_int(n: i64, n_bytes: usize) => (n, n_bytes));
+        $crate::impl_sink_methods!(@fn $mode $self
+            add_string(data: impl Into<String>) => (&Into::<String>::into(data)));
+        $crate::impl_sink_methods!(@fn $mode $self
+            add_string_with_prefix(data: impl Into<String>, prefix: $crate::options::LengthPrefix)
+            => (&Into::<String>::into(data), prefix));
+        $crate::impl_sink_methods!(@fn $mode $self
+            add_string_with_layout(data: impl Into<String>, layout: $crate::options::StringLayout)
+            => (&Into::<String>::into(data), layout));
+        $crate::impl_sink_methods!(@fn $mode $self
+            add_encoded_string(
+                data: impl Into<String>,
+                layout: $crate::options::StringLayout,
+                encoding: $crate::text::TextEncoding
+            ) => (&Into::<String>::into(data), layout, encoding));
+        $crate::impl_sink_methods!(@numbers $mode $self;
+            u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+        );
+        $crate::impl_sink_methods!(@unsigned $mode $self; u8, u16, u32, u64, u128, usize);
+        $crate::impl_sink_methods!(@signed $mode $self; i8, i16, i32, i64, i128, isize);
+    };
+    (@slices $mode:tt $self:tt) => {
+        /// See [`ByteSink::add_slice`](crate::ByteSink::add_slice).
+        pub fn add_slice<T: $crate::Encodable>(&mut $self, data: &[T]) -> $crate::utils::ParseResult<()> {
+            $crate::impl_sink_methods!(@call $mode $self add_slice(data))
+        }
+
+        /// See [`ByteSink::add_slice_with_prefix`](crate::ByteSink::add_slice_with_prefix).
+        pub fn add_slice_with_prefix<T: $crate::Encodable>(
+            &mut $self,
+            data: &[T],
+            prefix: $crate::options::LengthPrefix,
+        ) -> $crate::utils::ParseResult<()> {
+            $crate::impl_sink_methods!(@call $mode $self add_slice_with_prefix(data, prefix))
+        }
+    };
+    (@numbers $mode:tt $self:tt; $($t:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                $crate::impl_sink_methods!(@fn $mode $self [<add_ $t>](n: $t) => (n));
+            }
+        )*
+    };
+    (@unsigned $mode:tt $self:tt; $($t:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                $crate::impl_sink_methods!(@fn $mode $self [<add_leb128_ $t>](n: $t) => (n));
+            }
+        )*
+    };
+    (@signed $mode:tt $self:tt; $($t:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                $crate::impl_sink_methods!(@fn $mode $self [<add_leb128_ $t>](n: $t) => (n));
+                $crate::impl_sink_methods!(@fn $mode $self [<add_zigzag_ $t>](n: $t) => (n));
+            }
+        )*
+    };
+}
@@ -1,4 +1,9 @@
+pub mod bits;
 pub mod core;
+pub mod counting;
+pub mod fixed;
 pub mod helpers;
 pub mod macros;
+pub mod placeholder;
+pub mod sink;
 pub mod writers;
@@ -0,0 +1,190 @@
+use std::marker::PhantomData;
+
+use crate::{
+    encoder::{core::DataEncoder, sink::ByteSink},
+    errors::DataParseError,
+    impl_reserve,
+    options::LengthPrefix,
+    utils::{EndianSerialize, Endianness, ParseResult},
+};
+
+/// An unsigned integer type that can be reserved in a [`DataEncoder`] and written later.
+///
+/// Implemented for `u8`, `u16`, `u32` and `u64`, whose widths match the fixed-width
+/// [`LengthPrefix`] kinds.
+pub trait Reservable: EndianSerialize + Default {
+    /// The length prefix kind of the same width.
+    const PREFIX: LengthPrefix;
+}
+
+macro_rules! impl_reservable {
+    ($($t:ty => $prefix:ident),* $(,)?) => {
+        $(
+            impl Reservable for $t {
+                const PREFIX: LengthPrefix = LengthPrefix::$prefix;
+            }
+        )*
+    };
+}
+
+impl_reservable!(u8 => U8, u16 => U16, u32 => U32, u64 => U64);
+
+/// A handle to a zeroed field written by one of the `reserve_*` methods of [`DataEncoder`].
+///
+/// Pass it to [`DataEncoder::fill`] once the value is known. The field keeps the byte order
+/// the encoder was configured with when it was reserved.
+///
+/// A placeholder only fills the encoder that reserved it, and only while its field is still
+/// there: once a failed [`length_prefixed`](DataEncoder::length_prefixed) scope removes it,
+/// filling it is an error, even if other bytes have been written at its offset since.
+#[derive(Clone, Debug)]
+pub struct Placeholder<T> {
+    offset: usize,
+    endianness: Endianness,
+    /// Id of the encoder that reserved the field.
+    encoder: u64,
+    /// Number of rollbacks the encoder had gone through when the field was reserved.
+    generation: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Placeholder<T> {
+    /// Returns the offset of the reserved field in the encoder's buffer.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl DataEncoder {
+    /// Writes a zeroed `T` and returns a handle to fill it in later.
+    ///
+    /// The field is written exactly like the matching `add_*` method would write it, using
+    /// `endianness` for its byte order.
+    fn reserve<T: Reservable>(&mut self, endianness: Endianness) -> ParseResult<Placeholder<T>> {
+        ByteSink::add_item(self, &T::default().to_endian_bytes(&endianness))?;
+        Ok(Placeholder {
+            offset: self.buffer.len() - size_of::<T>(),
+            endianness,
+            encoder: self.id,
+            generation: self.rollbacks.len(),
+            _marker: PhantomData,
+        })
+    }
+
+    impl_reserve!(u8, u16, u32, u64);
+
+    /// Overwrites a field reserved with one of the `reserve_*` methods with `value`.
+    ///
+    /// # Errors
+    /// Returns an error if the placeholder was reserved by another encoder, or if its field
+    /// has been rolled back.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::DataEncoder;
+    /// let mut encoder = DataEncoder::new();
+    /// let count = encoder.reserve_u16()?;
+    /// for id in [7u8, 8, 9] {
+    ///     encoder.add_u8(id)?;
+    /// }
+    /// encoder.fill(count, 3)?;
+    /// assert_eq!(encoder.get_data()?, &vec![0x00, 0x03, 7, 8, 9]);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    pub fn fill<T: Reservable>(
+        &mut self,
+        placeholder: Placeholder<T>,
+        value: T,
+    ) -> ParseResult<()> {
+        if placeholder.encoder != self.id {
+            return Err(DataParseError::Custom {
+                e: "Placeholder was reserved by another encoder".into(),
+            });
+        }
+        let end = placeholder.offset + size_of::<T>();
+        if self.rollbacks[placeholder.generation..]
+            .iter()
+            .any(|&len| len < end)
+        {
+            return Err(DataParseError::Custom {
+                e: format!(
+                    "Reserved field at offset {} has been rolled back",
+                    placeholder.offset
+                ),
+            });
+        }
+        let bytes = value.to_endian_bytes(&placeholder.endianness);
+        self.patch(placeholder.offset, &bytes)
+    }
+
+    /// Writes a `P`-wide length prefix, runs `build_fn`, then patches the prefix in place with
+    /// the number of bytes `build_fn` wrote.
+    ///
+    /// The prefix is written exactly like [`add_length`](DataEncoder::add_length) would
+    /// write it, in the configured prefix byte order, without encoding the contents into a
+    /// separate buffer first. If `build_fn` fails, everything written by the scope is removed.
+    ///
+    /// # Errors
+    /// Returns the error of `build_fn`, or an error if the contents are too long for a
+    /// `P`-wide prefix.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{DataEncoder, EncodingOptions, utils::Endianness};
+    /// let mut encoder = DataEncoder::new();
+    /// encoder.set_options(&EncodingOptions::default().with_endianness(Endianness::LittleEndian));
+    /// encoder.length_prefixed::<u16>(|enc| {
+    ///     enc.add_u8(1)?;
+    ///     enc.add_u32(2)
+    /// })?;
+    /// assert_eq!(encoder.get_data()?, &vec![0x05, 0x00, 0x01, 0x02, 0x00, 0x00, 0x00]);
+    ///
+    /// // Too long for a `u8` prefix: the scope is rolled back.
+    /// assert!(encoder.length_prefixed::<u8>(|enc| enc.add_string("x".repeat(300))).is_err());
+    /// assert_eq!(encoder.get_data()?.len(), 7);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    pub fn length_prefixed<P: Reservable>(
+        &mut self,
+        build_fn: impl FnOnce(&mut DataEncoder) -> ParseResult<()>,
+    ) -> ParseResult<()> {
+        let start = self.buffer.len();
+        let result = self
+            .reserve::<P>(self.options.prefix_endianness().clone())
+            .and_then(|placeholder| {
+                let body = self.buffer.len();
+                build_fn(self)?;
+                let len = self
+                    .options
+                    .encode_length(self.buffer.len() - body, P::PREFIX)?;
+                self.patch(placeholder.offset, &len)
+            });
+        if result.is_err() {
+            self.rollback(start);
+        }
+        result
+    }
+
+    /// Cuts the buffer back to `len` bytes, invalidating the placeholders past it.
+    pub(crate) fn rollback(&mut self, len: usize) {
+        if len < self.buffer.len() {
+            self.buffer.truncate(len);
+            self.rollbacks.push(len);
+        }
+    }
+
+    /// Overwrites the bytes at `offset` with `bytes`.
+    fn patch(&mut self, offset: usize, bytes: &[u8]) -> ParseResult<()> {
+        let target = offset
+            .checked_add(bytes.len())
+            .and_then(|end| self.buffer.get_mut(offset..end))
+            .ok_or_else(|| DataParseError::Custom {
+                e: format!(
+                    "Reserved field at offset {} is no longer in the buffer",
+                    offset
+                ),
+            })?;
+        target.copy_from_slice(bytes);
+        Ok(())
+    }
+}
@@ -0,0 +1,450 @@
+use std::io::Write;
+
+#[cfg(feature = "async")]
+use crate::encoder::writers::async_writer::{core::AsyncDataWriter, helpers::AsyncEncodable};
+use crate::{
+    encoder::{
+        core::DataEncoder,
+        helpers::Encodable,
+        writers::sync_writer::{core::DataWriter, helpers::WriteEncodable},
+    },
+    errors::DataParseError,
+    impl_sink_defaults, impl_sink_encodable,
+    options::{EncodingOptions, LengthPrefix, StringLayout},
+    text::TextEncoding,
+    utils::{ParseResult, int_to_endian_bytes, uint_to_endian_bytes},
+};
+
+/// A destination that values can be encoded into.
+///
+/// `ByteSink` is implemented by [`DataEncoder`] (in-memory buffers), [`DataWriter`] (blocking
+/// streams), [`FixedEncoder`] (caller-provided buffers), `AsyncDataWriter` (async streams,
+/// which stages the bytes until its next async write or flush) and [`CountingSink`] (which
+/// only measures the encoded length).
+///
+/// Implementors only provide [`options`](ByteSink::options), [`put_bytes`](ByteSink::put_bytes)
+/// and [`len`](ByteSink::len). Every other method is provided on top of those, so the layout
+/// of each value lives in one place and a value encodes to the same bytes whatever the sink
+/// is. The inherent `add_*` methods of the sinks forward to these.
+///
+/// [`DataWriter`]: crate::encoder::writers::sync_writer::core::DataWriter
+pub trait ByteSink {
+    /// Returns the options the sink encodes with.
+    fn options(&self) -> &EncodingOptions;
+
+    /// Writes `data` as is, without any prefix.
+    fn put_bytes(&mut self, data: &[u8]) -> ParseResult<()>;
+
+    /// Returns the number of bytes written to the sink so far.
+    fn len(&self) -> usize;
+
+    /// Returns `true` if nothing has been written to the sink yet.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes the size prefix that precedes `len` bytes of data if `prepend_data_size` is set,
+    /// and nothing otherwise.
+    fn add_data_size(&mut self, len: usize) -> ParseResult<()> {
+        if self.options().prepend_data_size {
+            let prefix = self
+                .options()
+                .encode_length(len, self.options().length_prefix)?;
+            self.put_bytes(&prefix)?;
+        }
+        Ok(())
+    }
+
+    /// Writes raw bytes, preceded by their size if `prepend_data_size` is set.
+    ///
+    /// Every primitive value is written through this method.
+    fn add_item(&mut self, data: &[u8]) -> ParseResult<()> {
+        self.add_data_size(data.len())?;
+        self.put_bytes(data)
+    }
+
+    /// Writes `len` as a length prefix of the given kind.
+    ///
+    /// Fixed-width prefixes use the configured prefix endianness.
+    ///
+    /// # Errors
+    /// Returns an error if `len` doesn't fit in the prefix width.
+    fn add_length(&mut self, len: usize, prefix: LengthPrefix) -> ParseResult<()> {
+        let data = self.options().encode_length(len, prefix)?;
+        self.add_item(&data)
+    }
+
+    /// Writes a boolean as a single `0x00` or `0x01` byte.
+    fn add_bool(&mut self, data: bool) -> ParseResult<()> {
+        self.add_item(&[data as u8])
+    }
+
+    /// Writes `n` as an unsigned integer that is `n_bytes` wide (1 to 8), using the configured endianness.
+    ///
+    /// # Errors
+    /// Returns an error if `n_bytes` is out of range or `n` doesn't fit in `n_bytes` bytes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use dataparser_core::DataEncoder;
+    /// let mut encoder = DataEncoder::new();
+    /// encoder.add_uint(0x010203, 3)?;
+    /// encoder.add_int(-2, 3)?;
+    /// assert_eq!(encoder.get_data()?, &vec![0x01, 0x02, 0x03, 0xFF, 0xFF, 0xFE]);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    fn add_uint(&mut self, n: u64, n_bytes: usize) -> ParseResult<()> {
+        let data = uint_to_endian_bytes(n, n_bytes, &self.options().endianness)?;
+        self.add_item(&data)
+    }
+
+    /// Writes `n` as a two's complement signed integer that is `n_bytes` wide (1 to 8).
+    ///
+    /// # Errors
+    /// Returns an error if `n_bytes` is out of range or `n` doesn't fit in `n_bytes` bytes.
+    fn add_int(&mut self, n: i64, n_bytes: usize) -> ParseResult<()> {
+        let data = int_to_endian_bytes(n, n_bytes, &self.options().endianness)?;
+        self.add_item(&data)
+    }
+
+    impl_sink_defaults!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+    );
+
+    // `add_leb128_*` writes unsigned LEB128 for unsigned types and signed LEB128 for signed
+    // types, `add_zigzag_*` writes zigzag-encoded LEB128.
+    impl_sink_defaults!(@unsigned u8, u16, u32, u64, u128, usize);
+    impl_sink_defaults!(@signed i8, i16, i32, i64, i128, isize);
+
+    /// Writes a string value.
+    ///
+    /// The string is encoded with the configured text encoding (UTF-8 by default), preceded
+    /// by its encoded length (using the configured length prefix, a `u32` by default).
+    ///
+    /// # Example
+    /// ```rust
+    /// # use dataparser_core::DataEncoder;
+    /// # let mut encoder = DataEncoder::new();
+    /// encoder.add_string("hello")?;
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    ///
+    /// This is equivalent to calling `String::encode_data(...)` directly.
+    fn add_string(&mut self, data: impl AsRef<str>) -> ParseResult<()> {
+        self.add_string_with_prefix(data, self.options().length_prefix)
+    }
+
+    /// Writes a string like [`add_string`](ByteSink::add_string), using `prefix` instead of
+    /// the configured length prefix.
+    fn add_string_with_prefix(
+        &mut self,
+        data: impl AsRef<str>,
+        prefix: LengthPrefix,
+    ) -> ParseResult<()> {
+        let encoding = self.options().text_encoding;
+        write_string(
+            self,
+            data.as_ref(),
+            StringLayout::LengthPrefixed,
+            encoding,
+            prefix,
+        )
+    }
+
+    /// Writes a string using the given [`StringLayout`].
+    ///
+    /// - `LengthPrefixed`: same as [`add_string`](ByteSink::add_string)
+    /// - `NullTerminated`: the string bytes followed by a `\0` code unit
+    /// - `FixedWidth`: the string bytes padded with `pad` up to `width` bytes
+    /// - `RestOfInput`: the raw string bytes, with nothing to mark their end
+    ///
+    /// The configured text encoding is used; see
+    /// [`add_encoded_string`](ByteSink::add_encoded_string) to pick another one.
+    ///
+    /// # Errors
+    /// Returns an error if a null-terminated string contains a null character, if the
+    /// encoded string is longer than a fixed width, or if `strict_encoding` is set and the
+    /// string can't be represented in the text encoding.
+    fn add_string_with_layout(
+        &mut self,
+        data: impl AsRef<str>,
+        layout: StringLayout,
+    ) -> ParseResult<()> {
+        let encoding = self.options().text_encoding;
+        self.add_encoded_string(data, layout, encoding)
+    }
+
+    /// Writes a string using the given [`StringLayout`] and [`TextEncoding`].
+    fn add_encoded_string(
+        &mut self,
+        data: impl AsRef<str>,
+        layout: StringLayout,
+        encoding: TextEncoding,
+    ) -> ParseResult<()> {
+        let prefix = self.options().length_prefix;
+        write_string(self, data.as_ref(), layout, encoding, prefix)
+    }
+
+    /// Writes a count-prefixed slice whose items are each wrapped in a length-prefixed section.
+    ///
+    /// The format is:
+    /// - A length prefix indicating the number of items
+    /// - For each item:
+    ///     - A length prefix
+    ///     - The item's serialized bytes
+    ///
+    /// The prefixes use the configured [`LengthPrefix`]; see
+    /// [`add_slice_with_prefix`](ByteSink::add_slice_with_prefix) to override it.
+    fn add_slice<T: Encodable>(&mut self, data: &[T]) -> ParseResult<()>
+    where
+        Self: Sized,
+    {
+        self.add_slice_with_prefix(data, self.options().length_prefix)
+    }
+
+    /// Writes a slice like [`add_slice`](ByteSink::add_slice), using `prefix` instead of the
+    /// configured length prefix.
+    fn add_slice_with_prefix<T: Encodable>(
+        &mut self,
+        data: &[T],
+        prefix: LengthPrefix,
+    ) -> ParseResult<()>
+    where
+        Self: Sized,
+    {
+        self.add_length(data.len(), prefix)?;
+        for item in data {
+            self.add_slice_item(item, prefix)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a single item of a slice: its length, then the item itself.
+    ///
+    /// The length is computed with [`Encodable::encoded_len`] and the item is then written
+    /// straight into the sink, so nothing is buffered on the way.
+    ///
+    /// # Errors
+    /// Returns an error if the item doesn't write as many bytes as `encoded_len` reported.
+    fn add_slice_item<T: Encodable>(&mut self, item: &T, prefix: LengthPrefix) -> ParseResult<()>
+    where
+        Self: Sized,
+    {
+        let len = item.encoded_len(self.options())?;
+        self.add_length(len, prefix)?;
+        self.add_data_size(len)?;
+        let start = self.len();
+        item.encode_into(self)?;
+        let written = self.len() - start;
+        if written != len {
+            return Err(DataParseError::Custom {
+                e: format!(
+                    "Slice item wrote {} bytes, but its encoded length is {}",
+                    written, len
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Writes a presence flag, followed by the value if there is one.
+    fn add_option<T: Encodable>(&mut self, value: Option<&T>) -> ParseResult<()>
+    where
+        Self: Sized,
+    {
+        match value {
+            Some(value) => {
+                self.add_bool(true)?;
+                value.encode_into(self)
+            }
+            None => self.add_bool(false),
+        }
+    }
+}
+
+/// Writes `data` into `sink` using the given layout.
+///
+/// The encoded length is computed first, so the prefixes can be written before the text is
+/// encoded straight into the sink.
+fn write_string<S: ByteSink + ?Sized>(
+    sink: &mut S,
+    data: &str,
+    layout: StringLayout,
+    encoding: TextEncoding,
+    prefix: LengthPrefix,
+) -> ParseResult<()> {
+    if layout == StringLayout::NullTerminated && data.contains('\0') {
+        return Err(DataParseError::InvalidConversion {
+            e: "Null-terminated string contains a null character".into(),
+        });
+    }
+    let strict = sink.options().strict_encoding;
+    let text_len = encoding.encoded_len(data, strict)?;
+    let (len, padding) = match layout {
+        StringLayout::LengthPrefixed | StringLayout::RestOfInput => (text_len, 0),
+        StringLayout::NullTerminated => (text_len + encoding.unit_size(), 0),
+        StringLayout::FixedWidth { width, .. } => {
+            if text_len > width {
+                return Err(DataParseError::InvalidConversion {
+                    e: format!(
+                        "String of {} bytes does not fit in a fixed width of {}",
+                        text_len, width
+                    ),
+                });
+            }
+            (width, width - text_len)
+        }
+    };
+    if layout == StringLayout::LengthPrefixed {
+        sink.add_length(len, prefix)?;
+    }
+    sink.add_data_size(len)?;
+    encoding.encode_with(data, strict, |chunk| sink.put_bytes(chunk))?;
+    match layout {
+        StringLayout::NullTerminated => sink.put_bytes(&[0; 4][..encoding.unit_size()]),
+        StringLayout::FixedWidth { pad, .. } => {
+            let chunk = [pad; 64];
+            let mut left = padding;
+            while left > 0 {
+                let n = left.min(chunk.len());
+                sink.put_bytes(&chunk[..n])?;
+                left -= n;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// A type that can be encoded into any [`ByteSink`].
+///
+/// Implementing this trait once makes a type [`Encodable`] and [`WriteEncodable`] (and
+/// `AsyncEncodable` with the `async` feature), so it produces the same bytes whether it is
+/// written to a buffer or a stream. Prefer it over implementing those traits directly.
+///
+/// `Vec<T>`, `Option<T>` and arrays aren't `SinkEncodable` themselves, so that they stay
+/// [`Encodable`] for element types that only implement `Encodable`. Inside `encode_to`,
+/// write them with [`ByteSink::add_slice`] and [`ByteSink::add_option`].
+///
+/// # Example
+/// ```rust
+/// use dataparser_core::{
+///     ByteSink, DataEncoder, Decodable, Encodable, EncodingOptions, ParseOptions, ParseResult,
+///     SinkEncodable,
+///     encoder::writers::sync_writer::{core::DataWriter, helpers::WriteEncodable},
+/// };
+///
+/// struct Entry {
+///     id: u16,
+///     tags: Vec<String>,
+///     parent: Option<u32>,
+/// }
+///
+/// impl SinkEncodable for Entry {
+///     fn encode_to<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+///         sink.add_u16(self.id)?;
+///         sink.add_slice(&self.tags)?;
+///         sink.add_option(self.parent.as_ref())
+///     }
+/// }
+///
+/// let entry = Entry { id: 7, tags: vec!["a".into(), "bc".into()], parent: Some(1) };
+///
+/// let mut encoder = DataEncoder::new();
+/// entry.encode_data(&mut encoder)?;
+///
+/// let mut written = Vec::new();
+/// entry.to_writer(&mut DataWriter::new(&mut written))?;
+///
+/// assert_eq!(encoder.get_data()?, &written);
+/// assert_eq!(entry.to_bytes(&EncodingOptions::default())?, written);
+///
+/// // The same bytes decode back with the built-in decoders.
+/// let options = ParseOptions::default();
+/// let (id, rest) = u16::decode_prefix(&written, &options)?;
+/// let (tags, rest) = Vec::<String>::decode_prefix(rest, &options)?;
+/// assert_eq!((id, tags), (7, vec!["a".to_string(), "bc".to_string()]));
+/// assert_eq!(Option::<u32>::from_bytes(rest, &options)?, Some(1));
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub trait SinkEncodable {
+    fn encode_to<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()>;
+}
+
+impl<T: SinkEncodable> Encodable for T {
+    fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
+        self.encode_to(encoder)
+    }
+
+    fn encode_into<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        self.encode_to(sink)
+    }
+}
+
+impl<T: SinkEncodable> WriteEncodable for T {
+    fn to_writer<W: Write>(&self, encoder: &mut DataWriter<W>) -> ParseResult<()> {
+        self.encode_to(encoder)
+    }
+}
+
+/// The value is staged through the writer's [`ByteSink`] implementation, then sent.
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<T: SinkEncodable + Sync> AsyncEncodable for T {
+    async fn async_to_writer<W: tokio::io::AsyncWrite + Unpin + Send>(
+        &self,
+        writer: &mut AsyncDataWriter<W>,
+    ) -> ParseResult<()> {
+        writer.atomic(|writer| self.encode_to(writer))?;
+        writer.write_pending().await
+    }
+}
+
+impl ByteSink for DataEncoder {
+    fn options(&self) -> &EncodingOptions {
+        &self.options
+    }
+
+    fn put_bytes(&mut self, data: &[u8]) -> ParseResult<()> {
+        self.buffer.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.buffer.len()
+    }
+}
+
+impl<W: Write> ByteSink for DataWriter<W> {
+    fn options(&self) -> &EncodingOptions {
+        &self.options
+    }
+
+    fn put_bytes(&mut self, data: &[u8]) -> ParseResult<()> {
+        self.writer
+            .write_all(data)
+            .map_err(|e| DataParseError::IoError { e })?;
+        self.written += data.len();
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.written
+    }
+}
+
+impl SinkEncodable for String {
+    fn encode_to<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        sink.add_string(self.as_str())
+    }
+}
+
+impl SinkEncodable for bool {
+    fn encode_to<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        sink.add_bool(*self)
+    }
+}
+
+impl_sink_encodable!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
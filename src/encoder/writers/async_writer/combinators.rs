@@ -1,17 +1,77 @@
 use tokio::io::AsyncWrite;
 
-use crate::utils::ParseResult;
+use crate::{options::LengthPrefix, utils::ParseResult};
 
 use super::core::AsyncDataWriter;
 
 impl<W: AsyncWrite + Unpin> AsyncDataWriter<W> {
+    /// Writes `start`, then whatever `build_fn` writes, then `end`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use dataparser_core::encoder::writers::async_writer::core::AsyncDataWriter;
+    /// let mut written = Vec::new();
+    /// let mut writer = AsyncDataWriter::new(&mut written);
+    /// writer.add_between(b"<", b">", async |w| w.add_u16(0x0102).await).await?;
+    /// assert_eq!(written, vec![b'<', 0x01, 0x02, b'>']);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// # }).unwrap();
+    /// ```
     pub async fn add_between<F>(&mut self, start: &[u8], end: &[u8], build_fn: F) -> ParseResult<()>
     where
-        F: FnOnce(&mut AsyncDataWriter<W>) -> ParseResult<()>,
+        F: AsyncFnOnce(&mut AsyncDataWriter<W>) -> ParseResult<()>,
     {
         self.add_item(start).await?;
-        build_fn(self)?;
+        build_fn(self).await?;
         self.add_item(end).await?;
         Ok(())
     }
+
+    /// Writes whatever `build_fn` writes as a section prefixed with its length in bytes,
+    /// using the configured length prefix.
+    ///
+    /// See [`add_with_prefix`](AsyncDataWriter::add_with_prefix).
+    pub async fn add_with_length_prefix<F>(&mut self, build_fn: F) -> ParseResult<()>
+    where
+        F: AsyncFnOnce(&mut AsyncDataWriter<Vec<u8>>) -> ParseResult<()>,
+    {
+        self.add_with_prefix(self.options.length_prefix, build_fn)
+            .await
+    }
+
+    /// Writes whatever `build_fn` writes as a section prefixed with its length in bytes,
+    /// encoded as `prefix`.
+    ///
+    /// `build_fn` writes into an in-memory writer with the same options, so the length is
+    /// known before anything is sent. The section is laid out like a single item of
+    /// [`add_slice_with_prefix`](AsyncDataWriter::add_slice_with_prefix), and can be read
+    /// back with `parse_with_prefix`.
+    ///
+    /// # Example
+    /// ```rust
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use dataparser_core::{options::LengthPrefix, encoder::writers::async_writer::core::AsyncDataWriter};
+    /// let mut written = Vec::new();
+    /// let mut writer = AsyncDataWriter::new(&mut written);
+    /// writer
+    ///     .add_with_prefix(LengthPrefix::U8, async |section| {
+    ///         section.add_u8(1).await?;
+    ///         section.add_u16(2).await
+    ///     })
+    ///     .await?;
+    /// assert_eq!(written, vec![0x03, 0x01, 0x00, 0x02]);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn add_with_prefix<F>(&mut self, prefix: LengthPrefix, build_fn: F) -> ParseResult<()>
+    where
+        F: AsyncFnOnce(&mut AsyncDataWriter<Vec<u8>>) -> ParseResult<()>,
+    {
+        let mut section = AsyncDataWriter::with_options(Vec::new(), self.options.clone());
+        build_fn(&mut section).await?;
+        let data = section.into_inner().await?;
+        self.add_length(data.len(), prefix).await?;
+        self.add_item(data).await
+    }
 }
@@ -1,64 +1,163 @@
+use crate::encoder::core::DataEncoder;
+use crate::encoder::helpers::Encodable;
+use crate::encoder::sink::ByteSink;
 use crate::errors::DataParseError;
-use crate::impl_async_number;
-use crate::options::EncodingOptions;
-use crate::utils::{EndianSerialize, ParseResult};
+use crate::impl_sink_methods;
+use crate::options::{EncodingOptions, LengthPrefix};
+use crate::utils::ParseResult;
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-use super::helpers::AsyncEncodable;
-
 #[derive(Default, Clone)]
 pub struct AsyncDataWriter<W: AsyncWrite + Unpin> {
     pub(crate) options: EncodingOptions,
     pub(crate) writer: W,
+
+    /// Bytes written through [`ByteSink`] that haven't been sent to `writer` yet.
+    pub(crate) pending: DataEncoder,
+
+    /// Number of bytes sent to `writer` so far.
+    pub(crate) written: usize,
 }
 
 impl<W: AsyncWrite + Unpin> AsyncDataWriter<W> {
     pub fn new(writer: W) -> Self {
+        Self::with_options(writer, EncodingOptions::default())
+    }
+    pub fn with_options(writer: W, options: EncodingOptions) -> Self {
+        let mut pending = DataEncoder::new();
+        pending.set_options(&options);
         Self {
             writer,
-            options: EncodingOptions::default(),
+            options,
+            pending,
+            written: 0,
         }
     }
-    pub fn with_options(writer: W, options: EncodingOptions) -> Self {
-        Self { writer, options }
-    }
 
     pub fn set_options(&mut self, options: EncodingOptions) {
+        self.pending.set_options(&options);
         self.options = options;
     }
 
-    pub async fn add_item<T: AsRef<[u8]>>(&mut self, data: T) -> ParseResult<()> {
-        let data = data.as_ref();
-        if self.options.prepend_data_size {
-            let len = data.len() as u32;
+    /// Encodes `value` and writes it to the stream.
+    ///
+    /// The value is encoded exactly as [`DataEncoder`] would encode it with the same options,
+    /// so any [`Encodable`] type (including every [`SinkEncodable`] one) produces identical
+    /// bytes here.
+    ///
+    /// # Example
+    /// ```rust
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use dataparser_core::{
+    ///     Decodable, Encodable, EncodingOptions, ParseOptions,
+    ///     encoder::writers::async_writer::core::AsyncDataWriter,
+    /// };
+    /// let value = (vec!["a".to_string(), "bc".to_string()], Some(7u32));
+    /// let mut written = Vec::new();
+    /// let mut writer = AsyncDataWriter::new(&mut written);
+    /// writer.encode(&value.0).await?;
+    /// writer.encode(&value.1).await?;
+    ///
+    /// let options = EncodingOptions::default();
+    /// let mut expected = value.0.to_bytes(&options)?;
+    /// expected.extend(value.1.to_bytes(&options)?);
+    /// assert_eq!(written, expected);
+    ///
+    /// let (strings, rest) = Vec::<String>::decode_prefix(&written, &ParseOptions::default())?;
+    /// assert_eq!(strings, value.0);
+    /// assert_eq!(Option::<u32>::from_bytes(rest, &ParseOptions::default())?, value.1);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// [`SinkEncodable`]: crate::SinkEncodable
+    pub async fn encode<T: Encodable>(&mut self, value: &T) -> ParseResult<()> {
+        self.atomic(|writer| value.encode_data(&mut writer.pending))?;
+        self.write_pending().await
+    }
+
+    /// Writes the bytes staged through [`ByteSink`] and flushes the underlying writer.
+    pub async fn flush(&mut self) -> ParseResult<()> {
+        self.write_pending().await?;
+        self.writer.flush().await.map_err(DataParseError::from)
+    }
+
+    /// Writes the bytes staged through [`ByteSink`] and returns the underlying writer.
+    pub async fn into_inner(mut self) -> ParseResult<W> {
+        self.write_pending().await?;
+        Ok(self.writer)
+    }
+
+    /// Sends the bytes staged through [`ByteSink`] to the underlying writer.
+    pub(crate) async fn write_pending(&mut self) -> ParseResult<()> {
+        if !self.pending.buffer.is_empty() {
             self.writer
-                .write_all(&len.to_be_bytes())
+                .write_all(&self.pending.buffer)
                 .await
                 .map_err(DataParseError::from)?;
+            self.written += self.pending.buffer.len();
+            self.pending.buffer.clear();
         }
-        self.writer
-            .write_all(data)
-            .await
-            .map_err(DataParseError::from)?;
         Ok(())
     }
 
-    async fn add_num<T: EndianSerialize>(&mut self, n: T) -> ParseResult<()> {
-        let data = n.to_endian_bytes(&self.options.endianness);
-        self.add_item(data).await
+    /// Runs `f`, discarding whatever it staged if it fails.
+    pub(crate) fn atomic<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> ParseResult<T>,
+    ) -> ParseResult<T> {
+        let start = self.pending.buffer.len();
+        f(self).inspect_err(|_| self.pending.buffer.truncate(start))
     }
 
-    pub async fn add_slice<T: AsyncEncodable>(&mut self, items: &[T]) -> ParseResult<()> {
-        self.add_u32(items.len() as u32).await?;
+    /// Writes raw bytes, preceded by their size if `prepend_data_size` is set.
+    pub async fn add_item<T: AsRef<[u8]>>(&mut self, data: T) -> ParseResult<()> {
+        self.atomic(|writer| ByteSink::add_item(writer, data.as_ref()))?;
+        self.write_pending().await
+    }
+
+    pub async fn add_slice<T: Encodable>(&mut self, items: &[T]) -> ParseResult<()> {
+        self.add_slice_with_prefix(items, self.options.length_prefix)
+            .await
+    }
+
+    /// Serializes a slice in the same format as [`DataEncoder::add_slice_with_prefix`].
+    ///
+    /// Each item is written with [`ByteSink::add_slice_item`] and sent before the next one
+    /// is encoded, so at most one item is held in memory.
+    ///
+    /// [`DataEncoder::add_slice_with_prefix`]: crate::DataEncoder::add_slice_with_prefix
+    pub async fn add_slice_with_prefix<T: Encodable>(
+        &mut self,
+        items: &[T],
+        prefix: LengthPrefix,
+    ) -> ParseResult<()> {
+        self.add_length(items.len(), prefix).await?;
         for item in items {
-            let mut vec = Vec::new();
-            let mut temp = AsyncDataWriter::new(&mut vec);
-            temp.set_options(self.options.clone());
-            item.async_to_writer(&mut temp).await?;
-            self.add_u32(vec.len() as u32).await?;
-            self.add_item(vec).await?;
+            self.atomic(|writer| writer.add_slice_item(item, prefix))?;
+            self.write_pending().await?;
         }
         Ok(())
     }
-    impl_async_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+
+    // `add_*` methods for every value the writer can write; the layout of each one is
+    // defined by `ByteSink`.
+    impl_sink_methods!(async);
+}
+
+/// Values encoded through `ByteSink` are staged in memory, and sent on the next async write,
+/// [`encode`](AsyncDataWriter::encode) or [`flush`](AsyncDataWriter::flush).
+impl<W: AsyncWrite + Unpin> ByteSink for AsyncDataWriter<W> {
+    fn options(&self) -> &EncodingOptions {
+        &self.options
+    }
+
+    fn put_bytes(&mut self, data: &[u8]) -> ParseResult<()> {
+        self.pending.buffer.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.written + self.pending.buffer.len()
+    }
 }
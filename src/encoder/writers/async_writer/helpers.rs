@@ -1,23 +1,39 @@
-use crate::encoder::core::DataEncoder;
-use crate::impl_async_serializer;
+use crate::encoder::helpers::Encodable;
 use crate::utils::ParseResult;
 use async_trait::async_trait;
 use tokio::io::AsyncWrite;
 
 use super::core::AsyncDataWriter;
 
-impl<W: AsyncWrite + Unpin> AsyncDataWriter<W> {
-    pub async fn add_string(&mut self, data: impl Into<String>) -> ParseResult<()> {
-        let data = data.into();
-        self.add_u32(data.len() as u32).await?;
-        self.add_item(data).await
-    }
-
-    pub async fn add_bool(&mut self, data: bool) -> ParseResult<()> {
-        self.add_item(vec![data as u8]).await
-    }
-}
-
+/// A trait for types that can be serialized using an [`AsyncDataWriter`].
+///
+/// Every implementation in this crate writes the same bytes as the matching [`Encodable`]
+/// implementation does through a [`DataEncoder`] with the same options.
+///
+/// # Example
+/// ```rust
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// use dataparser_core::{
+///     Encodable, EncodingOptions, Endianness,
+///     encoder::writers::async_writer::{core::AsyncDataWriter, helpers::AsyncEncodable},
+/// };
+/// let value = vec!["a".to_string(), "bc".to_string()];
+/// let options = EncodingOptions::default().with_endianness(Endianness::LittleEndian);
+/// let mut written = Vec::new();
+/// let mut writer = AsyncDataWriter::with_options(&mut written, options.clone());
+/// value.async_to_writer(&mut writer).await?;
+/// Some(-2i64).async_to_writer(&mut writer).await?;
+///
+/// let mut expected = value.to_bytes(&options)?;
+/// expected.extend(Some(-2i64).to_bytes(&options)?);
+/// assert_eq!(written, expected);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`AsyncDataWriter`]: crate::encoder::writers::async_writer::core::AsyncDataWriter
+/// [`Encodable`]: crate::Encodable
+/// [`DataEncoder`]: crate::DataEncoder
 #[async_trait]
 pub trait AsyncEncodable {
     async fn async_to_writer<W: AsyncWrite + Unpin + Send>(
@@ -26,16 +42,6 @@ pub trait AsyncEncodable {
     ) -> ParseResult<()>;
 }
 
-#[async_trait]
-impl AsyncEncodable for u8 {
-    async fn async_to_writer<W: AsyncWrite + Unpin + Send>(
-        &self,
-        writer: &mut AsyncDataWriter<W>,
-    ) -> ParseResult<()> {
-        writer.add_u8(*self).await
-    }
-}
-
 #[async_trait]
 impl<T: AsyncEncodable + Send + Sync> AsyncEncodable for Option<T> {
     async fn async_to_writer<W: AsyncWrite + Unpin + Send>(
@@ -52,24 +58,17 @@ impl<T: AsyncEncodable + Send + Sync> AsyncEncodable for Option<T> {
     }
 }
 
-// For now, Vec<T> has to have the size prepended to it
+/// Encodes `Vec<T>` as an element count followed by each element in a length-prefixed
+/// section, like [`DataEncoder::add_slice`].
+///
+/// [`DataEncoder::add_slice`]: crate::DataEncoder::add_slice
 #[async_trait]
-impl<T: AsyncEncodable + Send + Sync> AsyncEncodable for Vec<T> {
+impl<T: Encodable + Sync> AsyncEncodable for Vec<T> {
     async fn async_to_writer<W: AsyncWrite + Unpin + Send>(
         &self,
         encoder: &mut AsyncDataWriter<W>,
     ) -> ParseResult<()> {
-        encoder.add_u32(self.len() as u32).await?;
-        for item in self {
-            let mut temp_encoder = DataEncoder::default();
-            temp_encoder.set_options(&encoder.options);
-            item.async_to_writer(&mut AsyncDataWriter::new(Vec::new()))
-                .await?;
-            let item_data = temp_encoder.get_data()?;
-            encoder.add_u32(item_data.len() as u32).await?;
-            encoder.add_item(item_data).await?;
-        }
-        Ok(())
+        encoder.add_slice(self).await
     }
 }
 
@@ -85,17 +84,3 @@ impl<T: AsyncEncodable + Send + Sync, const N: usize> AsyncEncodable for [T; N]
         Ok(())
     }
 }
-
-#[async_trait]
-impl AsyncEncodable for String {
-    async fn async_to_writer<W: AsyncWrite + Unpin + Send>(
-        &self,
-        encoder: &mut AsyncDataWriter<W>,
-    ) -> ParseResult<()> {
-        let len = self.len();
-        encoder.add_u32(len as u32).await?;
-        encoder.add_item(self).await
-    }
-}
-
-impl_async_serializer!(i16, isize);
@@ -1,4 +1,4 @@
 pub mod combinators;
 pub mod core;
 pub mod helpers;
-pub mod macros;
+pub mod sink;
@@ -0,0 +1,110 @@
+use std::io;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use futures_sink::Sink;
+use tokio::io::AsyncWrite;
+
+use crate::encoder::helpers::Encodable;
+use crate::errors::DataParseError;
+use crate::utils::ParseResult;
+
+use super::core::AsyncDataWriter;
+
+/// A [`Sink`] that encodes the values sent to it onto an [`AsyncDataWriter`].
+///
+/// Created with [`AsyncDataWriter::into_sink`]. Each value is encoded exactly as
+/// [`AsyncDataWriter::encode`] would encode it, back to back with no framing, so the output
+/// can be read back with a [`DecodeStream`] of the same type. Encoded values are staged in
+/// memory and sent to the underlying writer when the sink is flushed, closed, or made ready
+/// for the next value.
+///
+/// # Example
+/// ```rust
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// use dataparser_core::{
+///     encoder::writers::async_writer::core::AsyncDataWriter,
+///     parser::readers::async_reader::core::AsyncDataReader,
+/// };
+/// use futures::{SinkExt, StreamExt};
+///
+/// let (client, server) = tokio::io::duplex(64);
+/// let mut sink = AsyncDataWriter::new(client).into_sink::<String>();
+/// sink.send("hello".to_string()).await?;
+/// sink.send("world".to_string()).await?;
+/// sink.close().await?;
+///
+/// let stream = AsyncDataReader::new(server).into_stream::<String>();
+/// let messages: Vec<String> = stream.map(|item| item.unwrap()).collect().await;
+/// assert_eq!(messages, ["hello", "world"]);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`DecodeStream`]: crate::parser::readers::async_reader::stream::DecodeStream
+pub struct EncodeSink<W: AsyncWrite + Unpin, T> {
+    writer: AsyncDataWriter<W>,
+    _marker: PhantomData<fn(T)>,
+}
+
+impl<W: AsyncWrite + Unpin> AsyncDataWriter<W> {
+    /// Turns the writer into a [`Sink`] of `T` values.
+    pub fn into_sink<T: Encodable>(self) -> EncodeSink<W, T> {
+        EncodeSink {
+            writer: self,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W: AsyncWrite + Unpin, T> EncodeSink<W, T> {
+    /// Returns the underlying writer, along with any bytes it hasn't sent yet.
+    pub fn into_inner(self) -> AsyncDataWriter<W> {
+        self.writer
+    }
+
+    /// Sends the staged bytes to the underlying writer.
+    fn poll_write_pending(&mut self, cx: &mut Context<'_>) -> Poll<ParseResult<()>> {
+        let writer = &mut self.writer;
+        while !writer.pending.buffer.is_empty() {
+            let written =
+                ready!(Pin::new(&mut writer.writer).poll_write(cx, &writer.pending.buffer))?;
+            if written == 0 {
+                return Poll::Ready(Err(io::Error::from(io::ErrorKind::WriteZero).into()));
+            }
+            writer.pending.buffer.drain(..written);
+            writer.written += written;
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<W: AsyncWrite + Unpin, T: Encodable> Sink<T> for EncodeSink<W, T> {
+    type Error = DataParseError;
+
+    fn poll_ready(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ParseResult<()>> {
+        self.poll_write_pending(cx)
+    }
+
+    fn start_send(mut self: Pin<&mut Self>, item: T) -> ParseResult<()> {
+        let pending = &mut self.writer.pending;
+        let start = pending.buffer.len();
+        item.encode_data(pending)
+            .inspect_err(|_| pending.buffer.truncate(start))
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ParseResult<()>> {
+        ready!(self.poll_write_pending(cx))?;
+        Pin::new(&mut self.writer.writer)
+            .poll_flush(cx)
+            .map_err(DataParseError::from)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<ParseResult<()>> {
+        ready!(self.as_mut().poll_flush(cx))?;
+        Pin::new(&mut self.writer.writer)
+            .poll_shutdown(cx)
+            .map_err(DataParseError::from)
+    }
+}
@@ -1,6 +1,9 @@
 use std::io::Write;
 
-use crate::utils::ParseResult;
+use crate::{
+    encoder::{sink::ByteSink, writers::sync_writer::helpers::WriteEncodable},
+    utils::ParseResult,
+};
 
 use super::core::DataWriter;
 
@@ -9,9 +12,23 @@ impl<W: Write> DataWriter<W> {
     where
         F: FnOnce(&mut DataWriter<W>) -> ParseResult<()>,
     {
-        self.add_item(start)?;
+        ByteSink::add_item(self, start)?;
         build_fn(self)?;
-        self.add_item(end)?;
+        ByteSink::add_item(self, end)?;
         Ok(())
     }
+
+    /// Writes `tag` followed by whatever `payload` writes.
+    ///
+    /// The counterpart of the [`dispatch`] combinator.
+    ///
+    /// [`dispatch`]: crate::parser::combinators::dispatch
+    pub fn add_tagged<T, F>(&mut self, tag: T, payload: F) -> ParseResult<()>
+    where
+        T: WriteEncodable,
+        F: FnOnce(&mut DataWriter<W>) -> ParseResult<()>,
+    {
+        tag.to_writer(self)?;
+        payload(self)
+    }
 }
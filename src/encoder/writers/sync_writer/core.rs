@@ -1,28 +1,28 @@
 use crate::{
-    Encodable,
-    encoder::core::DataEncoder,
-    errors::DataParseError,
-    impl_number,
-    parser::EncodingOptions,
-    utils::{EndianSerialize, ParseResult},
+    Encodable, errors::DataParseError, impl_sink_methods, parser::EncodingOptions,
+    utils::ParseResult,
 };
 
 #[derive(Default, Clone)]
 pub struct DataWriter<W: std::io::Write> {
     pub(crate) options: EncodingOptions,
     pub(crate) writer: W,
+
+    /// Number of bytes written so far.
+    pub(crate) written: usize,
 }
 
 impl<W: std::io::Write> DataWriter<W> {
     pub fn new(writer: W) -> Self {
-        Self {
-            writer,
-            options: EncodingOptions::default(),
-        }
+        Self::with_options(writer, EncodingOptions::default())
     }
 
     pub fn with_options(writer: W, options: EncodingOptions) -> Self {
-        Self { writer, options }
+        Self {
+            writer,
+            options,
+            written: 0,
+        }
     }
     pub fn set_options(&mut self, options: EncodingOptions) {
         self.options = options;
@@ -35,41 +35,32 @@ impl<W: std::io::Write> DataWriter<W> {
         Ok(())
     }
 
-    pub(crate) fn add_item<T>(&mut self, data: T) -> ParseResult<()>
-    where
-        T: AsRef<[u8]>,
-    {
-        let data = data.as_ref();
-        if self.options.prepend_data_size {
-            let data_len = data.len() as u32;
-            self.writer
-                .write_all(&data_len.to_be_bytes())
-                .map_err(|e| DataParseError::IoError { e })?;
-        }
-        self.writer
-            .write_all(data)
-            .map_err(|e| DataParseError::IoError { e })?;
-        Ok(())
-    }
-
-    fn add_num<T: EndianSerialize>(&mut self, n: T) -> ParseResult<()> {
-        let data = n.to_endian_bytes(&self.options.endianness);
-        self.add_item(data)
-    }
-
-    pub fn add_slice<T: Encodable>(&mut self, data: &[T]) -> ParseResult<()> {
-        let data_len = data.len();
-        self.add_u32(data_len as u32)?;
-        for item in data {
-            let mut temp_encoder = DataEncoder::default();
-            temp_encoder.set_options(&self.options);
-            item.encode_data(&mut temp_encoder)?;
-            let built = temp_encoder.get_data()?;
-            self.add_u32(built.len() as u32)?;
-            self.add_item(built)?;
-        }
-        Ok(())
+    /// Encodes `value` and writes it to the stream.
+    ///
+    /// The value is encoded exactly as [`DataEncoder`] would encode it with the same options,
+    /// which lets types that only implement [`Encodable`] be written to a stream. It is
+    /// written with [`Encodable::encode_into`], so [`SinkEncodable`] types and containers of
+    /// them go straight to the stream.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{
+    ///     Encodable, EncodingOptions, encoder::writers::sync_writer::core::DataWriter,
+    /// };
+    /// let value = Some(vec![1u16, 2]);
+    /// let mut written = Vec::new();
+    /// DataWriter::new(&mut written).encode(&value)?;
+    /// assert_eq!(written, value.to_bytes(&EncodingOptions::default())?);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    ///
+    /// [`DataEncoder`]: crate::DataEncoder
+    /// [`SinkEncodable`]: crate::SinkEncodable
+    pub fn encode<T: Encodable>(&mut self, value: &T) -> ParseResult<()> {
+        value.encode_into(self)
     }
 
-    impl_number!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+    // `add_*` methods for every value the writer can write; the layout of each one is
+    // defined by `ByteSink`.
+    impl_sink_methods!();
 }
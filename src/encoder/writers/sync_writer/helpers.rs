@@ -1,13 +1,21 @@
 use std::io::Write;
 
-use crate::{encoder::core::DataEncoder, impl_write_encodable, utils::ParseResult};
+use crate::{Encodable, utils::ParseResult};
 
 use super::core::DataWriter;
 
+/// A trait for types that can be serialized using a [`DataWriter`].
+///
+/// Types implementing [`SinkEncodable`] get this trait for free, which is the preferred way
+/// to support writers: the same impl then also works for [`DataEncoder`].
+///
+/// [`SinkEncodable`]: crate::SinkEncodable
+/// [`DataEncoder`]: crate::DataEncoder
 pub trait WriteEncodable {
     fn to_writer<W: Write>(&self, encoder: &mut DataWriter<W>) -> ParseResult<()>;
 }
 
+/// Writes `Option<T>` as a boolean flag followed by the value (if present).
 impl<T: WriteEncodable> WriteEncodable for Option<T> {
     fn to_writer<W: Write>(&self, encoder: &mut DataWriter<W>) -> ParseResult<()> {
         match self {
@@ -15,29 +23,19 @@ impl<T: WriteEncodable> WriteEncodable for Option<T> {
                 encoder.add_bool(true)?;
                 value.to_writer(encoder)
             }
-            None => {
-                encoder.add_bool(false)?;
-                Ok(())
-            }
+            None => encoder.add_bool(false),
         }
     }
 }
 
-impl<T: WriteEncodable> WriteEncodable for Vec<T> {
+/// Writes `Vec<T>` in the same format as its [`Encodable`] implementation.
+impl<T: Encodable> WriteEncodable for Vec<T> {
     fn to_writer<W: Write>(&self, encoder: &mut DataWriter<W>) -> ParseResult<()> {
-        encoder.add_u32(self.len() as u32)?;
-        for item in self {
-            let mut temp_encoder = DataEncoder::default();
-            temp_encoder.set_options(&encoder.options);
-            item.to_writer(&mut DataWriter::new(Vec::new()))?;
-            let item_data = temp_encoder.get_data()?;
-            encoder.add_u32(item_data.len() as u32)?;
-            encoder.add_item(item_data)?;
-        }
-        Ok(())
+        encoder.add_slice(self)
     }
 }
 
+/// Writes arrays `[T; N]` by writing each element sequentially, without a length prefix.
 impl<T: WriteEncodable, const N: usize> WriteEncodable for [T; N] {
     fn to_writer<W: Write>(&self, encoder: &mut DataWriter<W>) -> ParseResult<()> {
         for item in self {
@@ -46,24 +44,3 @@ impl<T: WriteEncodable, const N: usize> WriteEncodable for [T; N] {
         Ok(())
     }
 }
-
-impl WriteEncodable for String {
-    fn to_writer<W: Write>(&self, encoder: &mut DataWriter<W>) -> ParseResult<()> {
-        encoder.add_u32(self.len() as u32)?;
-        encoder.add_item(self.as_bytes())
-    }
-}
-
-impl<W: Write> DataWriter<W> {
-    pub fn add_string(&mut self, data: impl Into<String>) -> ParseResult<()> {
-        let data: String = data.into();
-        self.add_u32(data.len() as u32)?;
-        self.add_item(data)
-    }
-
-    pub fn add_bool(&mut self, data: bool) -> ParseResult<()> {
-        self.add_item(vec![data as u8])
-    }
-}
-
-impl_write_encodable!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
@@ -1,10 +1,47 @@
-use std::io::{self, ErrorKind};
+use std::io;
 use thiserror::Error;
 
+use crate::options::Limit;
+
 #[derive(Debug, Error)]
 pub enum DataParseError {
+    /// Never returned by this crate.
+    #[deprecated(note = "truncated input is reported as `DataParseError::Incomplete`")]
     #[error("Unexpected binary EOF")]
     UnexpectedEOF,
+    /// The input ended before a read of `needed` bytes could complete.
+    ///
+    /// This means the data is truncated rather than malformed: once `needed - available`
+    /// more bytes arrive, the same read can succeed.
+    #[error("needed {needed} bytes, {available} available")]
+    Incomplete { needed: usize, available: usize },
+    /// A read ran past the end of a length-prefixed section.
+    ///
+    /// Unlike [`Incomplete`](DataParseError::Incomplete), more input won't help: the
+    /// section's declared length is too short for its contents.
+    #[error("section too short: needed {needed} bytes, {available} available")]
+    SectionOverrun { needed: usize, available: usize },
+    /// A resource limit configured in [`ParseOptions`](crate::options::ParseOptions) was exceeded.
+    #[error("{limit} limit exceeded: {value} > {max}")]
+    LimitExceeded {
+        limit: Limit,
+        value: usize,
+        max: usize,
+    },
+    /// Bytes were left over after decoding a value that should have used the whole input.
+    #[error("{len} bytes of trailing data")]
+    TrailingData { len: usize },
+    /// An encoder writing into a fixed-size buffer ran out of room.
+    ///
+    /// `required` is the buffer size the write needs in order to succeed.
+    #[error("buffer full: {required} bytes required, capacity is {capacity}")]
+    BufferFull { required: usize, capacity: usize },
+    /// A frame is longer than the maximum frame length of a framing codec.
+    #[cfg(feature = "async")]
+    #[error("frame of {len} bytes exceeds the maximum of {max}")]
+    FrameTooLong { len: usize, max: usize },
+    #[error("expected {expected}, found {actual}")]
+    Mismatch { expected: String, actual: String },
     #[error("Invalid conversion: {e}")]
     InvalidConversion { e: String },
     #[error("{e}")]
@@ -17,10 +54,154 @@ pub enum DataParseError {
     #[cfg(feature = "crypto")]
     #[error("Crypto error: {e}")]
     CryptoError { e: String },
+    /// An error tied to a byte offset in the input, with the context labels it occurred in
+    /// (outermost first).
+    #[error("at {offset:#x}{}: {error}", format_context(.context))]
+    At {
+        offset: usize,
+        context: Vec<String>,
+        #[source]
+        error: Box<DataParseError>,
+    },
+}
+
+fn format_context(context: &[String]) -> String {
+    if context.is_empty() {
+        String::new()
+    } else {
+        format!(" in {}", context.join("."))
+    }
+}
+
+impl DataParseError {
+    /// Attaches a byte offset to this error, unless it already has one.
+    pub fn at(self, offset: usize) -> Self {
+        match self {
+            located @ DataParseError::At { .. } => located,
+            error => DataParseError::At {
+                offset,
+                context: Vec::new(),
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// Adds an outer context label to this error, attaching `offset` if it has none yet.
+    ///
+    /// # Example
+    /// ```
+    /// use dataparser_core::DataParseError;
+    /// let error = DataParseError::Incomplete { needed: 4, available: 2 }
+    ///     .at(0x1c)
+    ///     .with_context("length", 0x18)
+    ///     .with_context("header", 0x10);
+    /// assert_eq!(error.to_string(), "at 0x1c in header.length: needed 4 bytes, 2 available");
+    /// ```
+    pub fn with_context(self, label: impl Into<String>, offset: usize) -> Self {
+        match self.at(offset) {
+            DataParseError::At {
+                offset,
+                mut context,
+                error,
+            } => {
+                context.insert(0, label.into());
+                DataParseError::At {
+                    offset,
+                    context,
+                    error,
+                }
+            }
+            _ => unreachable!("`at` always returns a located error"),
+        }
+    }
+
+    /// Returns the byte offset the error occurred at, if known.
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            DataParseError::At { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    /// Returns the context labels of the error, outermost first.
+    pub fn context(&self) -> &[String] {
+        match self {
+            DataParseError::At { context, .. } => context,
+            _ => &[],
+        }
+    }
+
+    /// Returns how many more bytes must arrive before the failed read can succeed, if this
+    /// error means the input is incomplete rather than malformed.
+    ///
+    /// # Example
+    /// ```
+    /// use dataparser_core::DataParser;
+    /// let mut parser = DataParser::new(&[0x00, 0x00, 0x00]);
+    /// let error = parser.get_u32().unwrap_err();
+    /// assert_eq!(error.missing_bytes(), Some(1));
+    /// ```
+    pub fn missing_bytes(&self) -> Option<usize> {
+        match self.kind() {
+            DataParseError::Incomplete { needed, available } => Some(needed - available),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the error means the input is truncated rather than malformed.
+    pub fn is_incomplete(&self) -> bool {
+        self.missing_bytes().is_some()
+    }
+
+    /// Returns the underlying error, without its offset and context.
+    pub fn kind(&self) -> &DataParseError {
+        match self {
+            DataParseError::At { error, .. } => error.kind(),
+            error => error,
+        }
+    }
+
+    /// Turns an [`Incomplete`](DataParseError::Incomplete) error raised inside a
+    /// length-prefixed section into a [`SectionOverrun`](DataParseError::SectionOverrun),
+    /// since the section itself was fully read.
+    pub(crate) fn in_section(self) -> Self {
+        match self {
+            DataParseError::Incomplete { needed, available } => {
+                DataParseError::SectionOverrun { needed, available }
+            }
+            DataParseError::At {
+                offset,
+                context,
+                error,
+            } => DataParseError::At {
+                offset,
+                context,
+                error: Box::new(error.in_section()),
+            },
+            error => error,
+        }
+    }
+
+    /// Moves the offset of a located error by `base`, e.g. to turn an offset within a
+    /// sub-parser into one within the enclosing input.
+    pub(crate) fn offset_by(self, base: usize) -> Self {
+        match self {
+            DataParseError::At {
+                offset,
+                context,
+                error,
+            } => DataParseError::At {
+                offset: offset + base,
+                context,
+                error,
+            },
+            error => error,
+        }
+    }
 }
 
 impl From<DataParseError> for io::Error {
     fn from(value: DataParseError) -> Self {
-        Self::new(ErrorKind::Other, value)
+        Self::other(value)
     }
 }
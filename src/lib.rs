@@ -14,7 +14,7 @@
 //!
 //! ## Example: Encode and Decode a Struct
 //! ```rust
-//! use libparser::{DataEncoder, DataParser, Encodable, Decodable, ParseResult};
+//! use dataparser_core::{DataEncoder, DataParser, Encodable, Decodable, ParseResult};
 //!
 //! #[derive(Debug)]
 //! struct Header {
@@ -31,7 +31,7 @@
 //! }
 //!
 //! impl Decodable for Header {
-//!     fn decode_data(parser: &mut DataParser) -> ParseResult<Self> {
+//!     fn from_parser(parser: &mut DataParser) -> ParseResult<Self> {
 //!         let id = parser.get_u32()?;
 //!         let flag = parser.get_bool()?;
 //!         Ok(Header { id, flag })
@@ -42,35 +42,49 @@
 //! ## Features
 //! - `derive`: Enables `#[derive(StructSerialize, StructDeserialize)]`
 //! - `crypto`: Enables AES-256 encryption with PKCS7 padding
-//! - `async` : Enables async stream reader/writer support via the tokio crate
+//! - `async` : Enables async stream reader/writer support via the tokio crate, including
+//!   `futures` `Stream`/`Sink` adapters and a `tokio-util` codec
 //!
 //! ## Modules
 //! - [`encoder`]: Binary serialization
 //! - [`parser`]: Binary deserialization
 //! - [`options`]: Runtime configuration for encoding/parsing
+//! - [`text`]: Text encodings for string fields
+//! - [`codec`]: Length-delimited `tokio-util` codec (optional)
 //! - [`crypto`]: AES encryption support (optional)
 //! - [`utils`]: Shared helpers, endian utilities
 //!
 //! ## Trait Overview
 //! - [`Encodable`] — custom serialization
+//! - [`SinkEncodable`] — serialization into any [`ByteSink`] (buffers and streams alike)
 //! - [`Decodable`] — custom deserialization
 //! - [`StreamDecodable`] — streaming-compatible deserialization
+//! - [`SourceDecodable`] — deserialization from any [`ByteSource`] (buffers and streams alike)
 //!
 //! [`encoder`]: crate::encoder
 //! [`parser`]: crate::parser
 //! [`options`]: crate::options
+//! [`text`]: crate::text
+//! [`codec`]: crate::codec
 //! [`crypto`]: crate::crypto
 //! [`utils`]: crate::utils
 //! [`Encodable`]: crate::Encodable
+//! [`SinkEncodable`]: crate::SinkEncodable
+//! [`ByteSink`]: crate::ByteSink
 //! [`Decodable`]: crate::Decodable
 //! [`StreamDecodable`]: crate::StreamDecodable
+//! [`SourceDecodable`]: crate::SourceDecodable
+//! [`ByteSource`]: crate::ByteSource
 // Core modules
 pub mod encoder;
 pub mod errors;
 pub mod options;
 pub mod parser;
+pub mod text;
 pub mod utils;
 
+#[cfg(feature = "async")]
+pub mod codec;
 #[cfg(feature = "crypto")]
 pub mod crypto;
 
@@ -78,12 +92,18 @@ pub mod crypto;
 pub use dataparser_derive::{StructDeserialize, StructSerialize};
 
 pub use encoder::helpers::Encodable;
+pub use encoder::sink::{ByteSink, SinkEncodable};
 pub use parser::helpers::Decodable;
 pub use parser::readers::sync_reader::helpers::StreamDecodable;
+pub use parser::source::{ByteSource, SourceDecodable};
 
 pub use encoder::core::DataEncoder;
 pub use errors::DataParseError;
 pub use options::{EncodingOptions, ParseOptions};
 pub use parser::core::DataParser;
-pub use utils::Endianness;
+pub use parser::frame::FrameDecoder;
 pub use utils::ParseResult;
+pub use utils::{BitOrder, Endianness, ReprEnum};
+
+#[doc(hidden)]
+pub use paste::paste as __paste;
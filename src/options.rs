@@ -5,10 +5,11 @@
 //!
 //! These options allow developers to customize:
 //! - Endianness (byte order)
-//! - Strict vs. lossy string decoding
+//! - Strict vs. lossy string decoding, and the text encoding of strings
 //! - Null string trimming
 //! - Verbose/custom error output
-//! - Length-prefixed field handling
+//! - Length-prefixed field handling and the width of length prefixes
+//! - Resource limits for decoding untrusted input
 //! - (Optionally) AES-256 encryption keys and IVs
 //!
 //! These options are passed to the core data processing types:
@@ -17,21 +18,165 @@
 //!
 //! # Example
 //! ```rust
-//! use libparser::options::ParseOptions;
-//! use libparser::utils::Endianness;
+//! use dataparser_core::options::ParseOptions;
 //!
 //! let options = ParseOptions::default()
 //!     .with_strict_encoding()
 //!     .with_trim_null_strings()
 //!     .with_length_prefixed_fields()
-//!     .with_verbose_errors();
+//!     .with_verbose_errors()
+//!     .with_max_collection_len(1024)
+//!     .with_max_alloc(1 << 20);
 //! ```
 //!
 //! [`ParseOptions`]: crate::options::ParseOptions
 //! [`EncodingOptions`]: crate::options::EncodingOptions
 //! [`DataParser`]: crate::parser::core::DataParser
 //! [`DataEncoder`]: crate::encoder::core::DataEncoder
-use crate::utils::Endianness;
+use crate::errors::DataParseError;
+use crate::text::TextEncoding;
+use crate::utils::{
+    Endianness, ParseResult, decode_uleb128, encode_uleb128, uint_from_endian_bytes,
+    uint_to_endian_bytes,
+};
+
+/// The encoding used for length prefixes of strings, vectors and length-prefixed sections.
+///
+/// Fixed-width prefixes use the prefix endianness configured in [`ParseOptions`] or
+/// [`EncodingOptions`], which defaults to the main `endianness` setting. `Varint` prefixes
+/// are unsigned LEB128 and have no byte order.
+///
+/// # Example
+/// ```
+/// use dataparser_core::{DataEncoder, DataParser, EncodingOptions, ParseOptions};
+/// use dataparser_core::options::LengthPrefix;
+///
+/// let mut encoder = DataEncoder::new();
+/// encoder.set_options(&EncodingOptions::default().with_length_prefix(LengthPrefix::U8));
+/// encoder.add_string("hi")?;
+/// assert_eq!(encoder.get_data()?, &vec![0x02, b'h', b'i']);
+///
+/// let options = ParseOptions::default().with_length_prefix(LengthPrefix::U8);
+/// let mut parser = DataParser::with_options(encoder.get_data()?, options);
+/// assert_eq!(parser.get_string(false)?, "hi");
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+///
+/// [`ParseOptions`]: crate::options::ParseOptions
+/// [`EncodingOptions`]: crate::options::EncodingOptions
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthPrefix {
+    /// A single byte.
+    U8,
+    /// Two bytes.
+    U16,
+    /// Four bytes (default).
+    #[default]
+    U32,
+    /// Eight bytes.
+    U64,
+    /// An unsigned LEB128 varint.
+    Varint,
+}
+
+/// How a string is laid out in the binary data.
+///
+/// Used by the `*_with_layout` string methods of the parsers and encoders.
+///
+/// # Example
+/// ```
+/// use dataparser_core::{DataEncoder, options::StringLayout};
+/// let mut encoder = DataEncoder::new();
+/// encoder.add_string_with_layout("ab", StringLayout::NullTerminated)?;
+/// encoder.add_string_with_layout("cd", StringLayout::FixedWidth { width: 4, pad: 0 })?;
+/// assert_eq!(encoder.get_data()?, &vec![b'a', b'b', 0, b'c', b'd', 0, 0]);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringLayout {
+    /// Preceded by its length, using the configured [`LengthPrefix`] (default).
+    #[default]
+    LengthPrefixed,
+    /// Followed by a null terminator (`\0`, or a null code unit for UTF-16).
+    NullTerminated,
+    /// Occupies exactly `width` bytes, padded at the end with `pad`.
+    FixedWidth { width: usize, pad: u8 },
+    /// Runs until the end of the input.
+    RestOfInput,
+}
+
+/// A resource limit of [`ParseOptions`], reported by [`DataParseError::LimitExceeded`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Limit {
+    /// Number of elements in a collection.
+    CollectionLength,
+    /// Length of a string in bytes.
+    StringLength,
+    /// Nesting depth of sections, collections and optional values.
+    Depth,
+    /// Total number of bytes allocated while decoding.
+    Allocation,
+    /// Number of bytes an incremental decoder buffers while waiting for a value to complete.
+    Buffered,
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Limit::CollectionLength => "collection length",
+            Limit::StringLength => "string length",
+            Limit::Depth => "nesting depth",
+            Limit::Allocation => "allocation",
+            Limit::Buffered => "buffered input",
+        };
+        f.write_str(name)
+    }
+}
+
+impl LengthPrefix {
+    /// Returns the size of the prefix in bytes, or `None` for variable-length prefixes.
+    pub fn width(&self) -> Option<usize> {
+        match self {
+            LengthPrefix::U8 => Some(1),
+            LengthPrefix::U16 => Some(2),
+            LengthPrefix::U32 => Some(4),
+            LengthPrefix::U64 => Some(8),
+            LengthPrefix::Varint => None,
+        }
+    }
+
+    /// Encodes `len` as a prefix of this kind.
+    ///
+    /// # Errors
+    /// Returns an error if `len` doesn't fit in the prefix width.
+    pub(crate) fn encode(&self, len: usize, endianness: &Endianness) -> ParseResult<Vec<u8>> {
+        match self.width() {
+            Some(width) => uint_to_endian_bytes(len as u64, width, endianness),
+            None => Ok(encode_uleb128(len as u128)),
+        }
+    }
+
+    /// Decodes a prefix of this kind, pulling bytes one at a time from `next`.
+    pub(crate) fn decode<F>(&self, endianness: &Endianness, mut next: F) -> ParseResult<usize>
+    where
+        F: FnMut() -> ParseResult<u8>,
+    {
+        let len = match self.width() {
+            Some(width) => {
+                let mut bytes = [0u8; 8];
+                for byte in bytes.iter_mut().take(width) {
+                    *byte = next()?;
+                }
+                uint_from_endian_bytes(&bytes[..width], endianness)?
+            }
+            None => decode_uleb128(u64::BITS, next)? as u64,
+        };
+        usize::try_from(len).map_err(|_| DataParseError::InvalidConversion {
+            e: format!("Length prefix {} does not fit in usize", len),
+        })
+    }
+}
+
 /// Configuration options used when parsing binary data using [`DataParser`].
 ///
 /// `ParseOptions` control how strings, numbers, and structural details are interpreted from
@@ -42,6 +187,7 @@ use crate::utils::Endianness;
 ///
 /// # Example
 /// ```
+/// # use dataparser_core::ParseOptions;
 /// let options = ParseOptions::default()
 ///     .with_strict_encoding()
 ///     .with_trim_null_strings()
@@ -71,9 +217,34 @@ pub struct ParseOptions {
     /// If `true`, expects fields to be prefixed with their length.
     pub(crate) length_prefixed_fields: bool,
 
-    /// If `true`, enables verbose, custom error reporting.
+    /// If `true`, conversion errors include the offending value.
     pub(crate) verbose_errors: bool,
 
+    /// Encoding of the length prefixes for strings, vectors and length-prefixed sections.
+    pub(crate) length_prefix: LengthPrefix,
+
+    /// Byte order of fixed-width length prefixes. `None` follows `endianness`.
+    pub(crate) length_prefix_endianness: Option<Endianness>,
+
+    /// Text encoding of string fields (UTF-8 by default).
+    pub(crate) text_encoding: TextEncoding,
+
+    /// Maximum number of elements in a decoded collection. `None` means unlimited.
+    pub(crate) max_collection_len: Option<usize>,
+
+    /// Maximum length in bytes of a decoded string. `None` means unlimited.
+    pub(crate) max_string_len: Option<usize>,
+
+    /// Maximum nesting depth of sections, collections and optional values. `None` means unlimited.
+    pub(crate) max_depth: Option<usize>,
+
+    /// Maximum number of bytes allocated over the lifetime of a parser. `None` means unlimited.
+    pub(crate) max_alloc: Option<usize>,
+
+    /// Maximum number of bytes an incremental decoder buffers for a single value. `None`
+    /// means unlimited.
+    pub(crate) max_buffered: Option<usize>,
+
     /// AES-256 key for decryption (only available with `crypto` feature).
     #[cfg(feature = "crypto")]
     pub(crate) key: Vec<u8>,
@@ -91,6 +262,14 @@ impl Default for ParseOptions {
             strict_encoding: false,
             length_prefixed_fields: false,
             verbose_errors: false,
+            length_prefix: LengthPrefix::default(),
+            length_prefix_endianness: None,
+            text_encoding: TextEncoding::default(),
+            max_collection_len: None,
+            max_string_len: None,
+            max_depth: None,
+            max_alloc: None,
+            max_buffered: None,
             #[cfg(feature = "crypto")]
             key: Vec::new(),
             #[cfg(feature = "crypto")]
@@ -145,6 +324,121 @@ impl ParseOptions {
         self.length_prefixed_fields = false;
     }
 
+    /// Sets the encoding of length prefixes (e.g. for strings and `Vec<T>`).
+    pub fn set_length_prefix(&mut self, prefix: LengthPrefix) {
+        self.length_prefix = prefix;
+    }
+
+    /// Sets the byte order of fixed-width length prefixes, independently of `endianness`.
+    pub fn set_length_prefix_endianness(&mut self, endian: Endianness) {
+        self.length_prefix_endianness = Some(endian);
+    }
+
+    /// Returns the byte order used for fixed-width length prefixes.
+    pub(crate) fn prefix_endianness(&self) -> &Endianness {
+        self.length_prefix_endianness
+            .as_ref()
+            .unwrap_or(&self.endianness)
+    }
+
+    /// Sets the text encoding used for string fields.
+    pub fn set_text_encoding(&mut self, encoding: TextEncoding) {
+        self.text_encoding = encoding;
+    }
+
+    /// Limits the number of elements a decoded collection may have.
+    pub fn set_max_collection_len(&mut self, max: usize) {
+        self.max_collection_len = Some(max);
+    }
+
+    /// Removes the collection length limit.
+    pub fn unset_max_collection_len(&mut self) {
+        self.max_collection_len = None;
+    }
+
+    /// Limits the length in bytes of decoded strings.
+    pub fn set_max_string_len(&mut self, max: usize) {
+        self.max_string_len = Some(max);
+    }
+
+    /// Removes the string length limit.
+    pub fn unset_max_string_len(&mut self) {
+        self.max_string_len = None;
+    }
+
+    /// Limits how deeply sections, collections and optional values may be nested.
+    pub fn set_max_depth(&mut self, max: usize) {
+        self.max_depth = Some(max);
+    }
+
+    /// Removes the nesting depth limit.
+    pub fn unset_max_depth(&mut self) {
+        self.max_depth = None;
+    }
+
+    /// Limits the total number of bytes a parser may allocate for decoded data.
+    pub fn set_max_alloc(&mut self, max: usize) {
+        self.max_alloc = Some(max);
+    }
+
+    /// Removes the allocation limit.
+    pub fn unset_max_alloc(&mut self) {
+        self.max_alloc = None;
+    }
+
+    /// Limits how many bytes incremental decoders ([`FrameDecoder`], and the async reader's
+    /// `decode` and `into_stream`) buffer while waiting for a single value to complete.
+    ///
+    /// [`DataReader`] also checks it against every length-prefixed section (such as a vector
+    /// item) it reads into memory before decoding it. A [`DataParser`] already holds its
+    /// whole input, so the limit doesn't apply to it.
+    ///
+    /// [`FrameDecoder`]: crate::parser::frame::FrameDecoder
+    /// [`DataReader`]: crate::parser::readers::sync_reader::core::DataReader
+    /// [`DataParser`]: crate::DataParser
+    pub fn set_max_buffered(&mut self, max: usize) {
+        self.max_buffered = Some(max);
+    }
+
+    /// Removes the buffered input limit.
+    pub fn unset_max_buffered(&mut self) {
+        self.max_buffered = None;
+    }
+
+    /// Checks `value` against the given limit.
+    ///
+    /// # Errors
+    /// Returns [`DataParseError::LimitExceeded`] if the limit is set and `value` is above it.
+    pub(crate) fn check_limit(&self, limit: Limit, value: usize) -> ParseResult<()> {
+        let max = match limit {
+            Limit::CollectionLength => self.max_collection_len,
+            Limit::StringLength => self.max_string_len,
+            Limit::Depth => self.max_depth,
+            Limit::Allocation => self.max_alloc,
+            Limit::Buffered => self.max_buffered,
+        };
+        match max {
+            Some(max) if value > max => Err(DataParseError::LimitExceeded { limit, value, max }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolves the `utf16` flag taken by the string getters into a text encoding.
+    ///
+    /// `true` selects UTF-16 in the configured `endianness`; `false` selects the
+    /// configured `text_encoding`.
+    pub(crate) fn string_encoding(&self, utf16: bool) -> TextEncoding {
+        if !utf16 {
+            return self.text_encoding;
+        }
+        match self.endianness {
+            Endianness::BigEndian => TextEncoding::Utf16Be,
+            Endianness::LittleEndian => TextEncoding::Utf16Le,
+            Endianness::NativeEndian if cfg!(target_endian = "big") => TextEncoding::Utf16Be,
+            Endianness::NativeEndian => TextEncoding::Utf16Le,
+        }
+    }
+
     /// Enables trimming and returns updated options (builder-style).
     pub fn with_trim_null_strings(mut self) -> Self {
         self.trim_null_strings = true;
@@ -168,6 +462,54 @@ impl ParseOptions {
         self.length_prefixed_fields = true;
         self
     }
+
+    /// Sets the encoding of length prefixes and returns updated options.
+    pub fn with_length_prefix(mut self, prefix: LengthPrefix) -> Self {
+        self.length_prefix = prefix;
+        self
+    }
+
+    /// Sets the byte order of fixed-width length prefixes and returns updated options.
+    pub fn with_length_prefix_endianness(mut self, endian: Endianness) -> Self {
+        self.length_prefix_endianness = Some(endian);
+        self
+    }
+
+    /// Sets the text encoding used for string fields and returns updated options.
+    pub fn with_text_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.text_encoding = encoding;
+        self
+    }
+
+    /// Limits the number of elements in decoded collections and returns updated options.
+    pub fn with_max_collection_len(mut self, max: usize) -> Self {
+        self.max_collection_len = Some(max);
+        self
+    }
+
+    /// Limits the length of decoded strings and returns updated options.
+    pub fn with_max_string_len(mut self, max: usize) -> Self {
+        self.max_string_len = Some(max);
+        self
+    }
+
+    /// Limits the nesting depth and returns updated options.
+    pub fn with_max_depth(mut self, max: usize) -> Self {
+        self.max_depth = Some(max);
+        self
+    }
+
+    /// Limits the total number of bytes allocated and returns updated options.
+    pub fn with_max_alloc(mut self, max: usize) -> Self {
+        self.max_alloc = Some(max);
+        self
+    }
+
+    /// Limits the bytes buffered by incremental decoders and returns updated options.
+    pub fn with_max_buffered(mut self, max: usize) -> Self {
+        self.max_buffered = Some(max);
+        self
+    }
 }
 
 /// Configuration options used when encoding data using [`DataEncoder`].
@@ -179,6 +521,7 @@ impl ParseOptions {
 ///
 /// # Example
 /// ```
+/// # use dataparser_core::{EncodingOptions, Endianness};
 /// let options = EncodingOptions::default().with_prepended_data_size().with_endianness(Endianness::LittleEndian);
 /// ```
 ///
@@ -188,9 +531,22 @@ pub struct EncodingOptions {
     /// Controls the byte order used for numeric encoding.
     pub(crate) endianness: Endianness,
 
-    /// If `true`, every `add_item(...)` call prepends a `u32` size prefix.
+    /// If `true`, every `add_item(...)` call prepends a size prefix (see `length_prefix`).
     pub(crate) prepend_data_size: bool,
 
+    /// Encoding of the length prefixes for strings, slices and prepended data sizes.
+    pub(crate) length_prefix: LengthPrefix,
+
+    /// Byte order of fixed-width length prefixes. `None` follows `endianness`.
+    pub(crate) length_prefix_endianness: Option<Endianness>,
+
+    /// Text encoding of string fields (UTF-8 by default).
+    pub(crate) text_encoding: TextEncoding,
+
+    /// If `true`, encoding a character the text encoding can't represent is an error.
+    /// If `false`, it is replaced with `?`.
+    pub(crate) strict_encoding: bool,
+
     /// AES-256 key used for encryption (if crypto is enabled).
     #[cfg(feature = "crypto")]
     pub(crate) key: Vec<u8>,
@@ -205,6 +561,10 @@ impl Default for EncodingOptions {
         Self {
             endianness: Endianness::BigEndian,
             prepend_data_size: false,
+            length_prefix: LengthPrefix::default(),
+            length_prefix_endianness: None,
+            text_encoding: TextEncoding::default(),
+            strict_encoding: false,
             #[cfg(feature = "crypto")]
             key: Vec::new(),
             #[cfg(feature = "crypto")]
@@ -214,7 +574,7 @@ impl Default for EncodingOptions {
 }
 
 impl EncodingOptions {
-    /// Enables data size prefixing for all encoded items (as `u32`).
+    /// Enables data size prefixing for all encoded items (using the configured length prefix).
     pub fn set_prepended_data_size(&mut self) {
         self.prepend_data_size = true;
     }
@@ -229,6 +589,43 @@ impl EncodingOptions {
         self.endianness = endianness;
     }
 
+    /// Sets the encoding of length prefixes (e.g. for strings and slices).
+    pub fn set_length_prefix(&mut self, prefix: LengthPrefix) {
+        self.length_prefix = prefix;
+    }
+
+    /// Sets the byte order of fixed-width length prefixes, independently of `endianness`.
+    pub fn set_length_prefix_endianness(&mut self, endianness: Endianness) {
+        self.length_prefix_endianness = Some(endianness);
+    }
+
+    /// Returns the byte order used for fixed-width length prefixes.
+    pub(crate) fn prefix_endianness(&self) -> &Endianness {
+        self.length_prefix_endianness
+            .as_ref()
+            .unwrap_or(&self.endianness)
+    }
+
+    /// Sets the text encoding used for string fields.
+    pub fn set_text_encoding(&mut self, encoding: TextEncoding) {
+        self.text_encoding = encoding;
+    }
+
+    /// Makes unrepresentable characters an error when encoding strings.
+    pub fn set_strict_encoding(&mut self) {
+        self.strict_encoding = true;
+    }
+
+    /// Replaces unrepresentable characters with `?` when encoding strings.
+    pub fn unset_strict_encoding(&mut self) {
+        self.strict_encoding = false;
+    }
+
+    /// Encodes `len` using the given prefix kind and the configured prefix byte order.
+    pub(crate) fn encode_length(&self, len: usize, prefix: LengthPrefix) -> ParseResult<Vec<u8>> {
+        prefix.encode(len, self.prefix_endianness())
+    }
+
     /// Enables size prefixing and returns updated options (builder-style).
     pub fn with_prepended_data_size(mut self) -> Self {
         self.prepend_data_size = true;
@@ -240,4 +637,28 @@ impl EncodingOptions {
         self.endianness = endianness;
         self
     }
+
+    /// Sets the encoding of length prefixes and returns updated options (builder-style).
+    pub fn with_length_prefix(mut self, prefix: LengthPrefix) -> Self {
+        self.length_prefix = prefix;
+        self
+    }
+
+    /// Sets the byte order of fixed-width length prefixes and returns updated options (builder-style).
+    pub fn with_length_prefix_endianness(mut self, endianness: Endianness) -> Self {
+        self.length_prefix_endianness = Some(endianness);
+        self
+    }
+
+    /// Sets the text encoding used for string fields and returns updated options (builder-style).
+    pub fn with_text_encoding(mut self, encoding: TextEncoding) -> Self {
+        self.text_encoding = encoding;
+        self
+    }
+
+    /// Enables strict string encoding and returns updated options (builder-style).
+    pub fn with_strict_encoding(mut self) -> Self {
+        self.strict_encoding = true;
+        self
+    }
 }
@@ -0,0 +1,106 @@
+use crate::errors::DataParseError;
+use crate::parser::core::DataParser;
+use crate::utils::{BitOrder, ParseResult};
+
+/// A bit-granular view over a [`DataParser`].
+///
+/// `BitParser` borrows the parser and consumes whole bytes from it as bits are requested.
+/// Bits left over in a partially read byte are discarded by [`align`] or [`finish`], after
+/// which the byte-level API continues from the next unread byte.
+///
+/// # Example
+/// ```rust
+/// use dataparser_core::{DataParser, utils::BitOrder};
+/// // IPv4: version (4 bits), IHL (4 bits), then a byte-aligned DSCP/ECN field
+/// let mut parser = DataParser::new(&[0x45, 0x00]);
+/// let mut bits = parser.bits(BitOrder::MsbFirst);
+/// assert_eq!(bits.read_bits(4).unwrap(), 4);
+/// assert_eq!(bits.read_bits(4).unwrap(), 5);
+/// let parser = bits.finish();
+/// assert_eq!(parser.get_u8().unwrap(), 0x00);
+/// ```
+///
+/// [`align`]: BitParser::align
+/// [`finish`]: BitParser::finish
+pub struct BitParser<'p, 'a> {
+    parser: &'p mut DataParser<'a>,
+    order: BitOrder,
+    current: u8,
+    bits_left: u32,
+}
+
+impl<'p, 'a> BitParser<'p, 'a> {
+    /// Creates a bit reader over `parser`, starting at its current byte position.
+    pub fn new(parser: &'p mut DataParser<'a>, order: BitOrder) -> Self {
+        Self {
+            parser,
+            order,
+            current: 0,
+            bits_left: 0,
+        }
+    }
+
+    /// Reads `n` bits (at most 64) and returns them as the low bits of a `u64`.
+    ///
+    /// # Errors
+    /// Returns an error if `n` is greater than 64 or the underlying parser runs out of bytes.
+    pub fn read_bits(&mut self, n: u32) -> ParseResult<u64> {
+        if n > u64::BITS {
+            return Err(DataParseError::Custom {
+                e: format!("Cannot read {} bits into a u64", n),
+            });
+        }
+        let mut value = 0u64;
+        let mut read = 0;
+        while read < n {
+            if self.bits_left == 0 {
+                self.current = self.parser.get_byte()?;
+                self.bits_left = 8;
+            }
+            let take = (n - read).min(self.bits_left);
+            let mask = (1u64 << take) - 1;
+            match self.order {
+                BitOrder::MsbFirst => {
+                    let chunk = (self.current >> (self.bits_left - take)) as u64 & mask;
+                    value = (value << take) | chunk;
+                }
+                BitOrder::LsbFirst => {
+                    let chunk = (self.current >> (8 - self.bits_left)) as u64 & mask;
+                    value |= chunk << read;
+                }
+            }
+            self.bits_left -= take;
+            read += take;
+        }
+        Ok(value)
+    }
+
+    /// Reads a single bit and interprets it as a boolean.
+    pub fn read_bit(&mut self) -> ParseResult<bool> {
+        Ok(self.read_bits(1)? != 0)
+    }
+
+    /// Returns `true` if no bits of a partially read byte are pending.
+    pub fn is_aligned(&self) -> bool {
+        self.bits_left == 0
+    }
+
+    /// Discards the remaining bits of the current byte, so the next read starts on a byte boundary.
+    pub fn align(&mut self) {
+        self.bits_left = 0;
+    }
+
+    /// Re-aligns to the next byte boundary and hands the underlying parser back.
+    pub fn finish(self) -> &'p mut DataParser<'a> {
+        self.parser
+    }
+}
+
+impl<'a> DataParser<'a> {
+    /// Starts reading at bit granularity from the current position.
+    ///
+    /// See [`BitParser`] for details.
+    pub fn bits(&mut self, order: BitOrder) -> BitParser<'_, 'a> {
+        BitParser::new(self, order)
+    }
+}
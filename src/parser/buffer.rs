@@ -2,38 +2,69 @@ use std::ops::{Deref, DerefMut};
 
 /// A flexible, owned-or-borrowed buffer abstraction for in-place encoding and decoding.
 ///
-/// `Buffer` allows you to either borrow a mutable buffer (`&'a mut [u8]`) or
-/// own the buffer outright (`Box<[u8]>`). This is useful for encoding APIs
-/// that want to support zero-copy or heap-allocated storage interchangeably.
+/// `Buffer` allows you to either borrow a mutable buffer (`&'a mut [u8]`), borrow a
+/// read-only buffer (`&'a [u8]`), or own the buffer outright (`Box<[u8]>`). This is useful
+/// for encoding APIs that want to support zero-copy or heap-allocated storage interchangeably.
 ///
 /// ## Example
 /// ```
+/// use dataparser_core::parser::buffer::Buffer;
 /// let mut scratch = [0u8; 32];
 /// let buf = Buffer::from(&mut scratch[..]); // Borrowed
+/// let buf = Buffer::from(&[0u8; 32][..]);   // Shared
 /// let buf = Buffer::from(vec![0u8; 32]);    // Owned
 /// ```
 pub enum Buffer<'a> {
     /// A mutable borrowed buffer slice.
     Borrowed(&'a mut [u8]),
+    /// A read-only borrowed buffer slice.
+    ///
+    /// Mutable access copies the data into an owned buffer first.
+    Shared(&'a [u8]),
     /// An owned heap-allocated buffer.
     Owned(Box<[u8]>),
 }
 
-impl Buffer<'_> {
+impl<'a> Buffer<'a> {
     pub fn as_slice(&self) -> &[u8] {
         match self {
             Buffer::Borrowed(buf) => buf,
+            Buffer::Shared(buf) => buf,
             Buffer::Owned(buf) => buf,
         }
     }
 
+    /// Returns the buffer as a mutable slice.
+    ///
+    /// A `Shared` buffer is copied into an owned buffer before being handed out,
+    /// so the original input is never modified.
     pub fn as_mut_slice(&mut self) -> &mut [u8] {
+        if let Buffer::Shared(buf) = self {
+            *self = Buffer::Owned(Box::from(*buf));
+        }
         match self {
             Buffer::Borrowed(buf) => buf,
+            Buffer::Shared(_) => unreachable!("shared buffers are converted above"),
             Buffer::Owned(buf) => buf,
         }
     }
 
+    /// Returns the underlying data with the full input lifetime `'a`, if the buffer is borrowed.
+    ///
+    /// A `Borrowed` buffer is downgraded to `Shared` in the process, since a read-only
+    /// reference to the input can't coexist with a mutable one. Returns `None` for
+    /// `Owned` buffers, which have no input lifetime to borrow from.
+    pub fn as_shared(&mut self) -> Option<&'a [u8]> {
+        if let Buffer::Borrowed(buf) = self {
+            let buf: &'a mut [u8] = std::mem::take(buf);
+            *self = Buffer::Shared(buf);
+        }
+        match self {
+            Buffer::Shared(buf) => Some(*buf),
+            _ => None,
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.as_slice().len()
     }
@@ -73,6 +104,24 @@ impl<'a> From<&'a mut Vec<u8>> for Buffer<'a> {
     }
 }
 
+impl<'a> From<&'a [u8]> for Buffer<'a> {
+    fn from(value: &'a [u8]) -> Self {
+        Buffer::Shared(value)
+    }
+}
+
+impl<'a, const N: usize> From<&'a [u8; N]> for Buffer<'a> {
+    fn from(value: &'a [u8; N]) -> Self {
+        Buffer::Shared(value)
+    }
+}
+
+impl<'a> From<&'a Vec<u8>> for Buffer<'a> {
+    fn from(value: &'a Vec<u8>) -> Self {
+        Buffer::Shared(value.as_slice())
+    }
+}
+
 impl Default for Buffer<'_> {
     fn default() -> Self {
         Self::Owned(Box::default())
@@ -1,7 +1,73 @@
+//! Parser combinators.
+//!
+//! Every combinator here takes and returns parsers of the form
+//! `Fn(&mut DataParser) -> ParseResult<T>`, so they nest freely. When a combinator fails,
+//! the cursor is restored to where it was before the combinator ran, so a failed branch
+//! never leaves the parser half-way through its input.
+//!
+//! # Example
+//! ```
+//! use dataparser_core::{DataParser, ParseResult};
+//! use dataparser_core::parser::combinators::{alt, many0, map, preceded, verify};
+//!
+//! fn small(p: &mut DataParser) -> ParseResult<u32> {
+//!     map(verify(|p: &mut DataParser| p.get_u8(), |b| *b < 0x80), u32::from)(p)
+//! }
+//! fn large(p: &mut DataParser) -> ParseResult<u32> {
+//!     preceded(|p: &mut DataParser| p.get_u8(), |p: &mut DataParser| p.get_u32())(p)
+//! }
+//!
+//! let mut parser = DataParser::new(&[0x05, 0xFF, 0x00, 0x00, 0x01, 0x00, 0x07]);
+//! let values = many0(alt((small, large)))(&mut parser)?;
+//! assert_eq!(values, vec![5, 256, 7]);
+//! # Ok::<(), dataparser_core::DataParseError>(())
+//! ```
+use std::fmt::Debug;
+
 use crate::errors::DataParseError;
+use crate::impl_combinator_tuple;
+use crate::options::LengthPrefix;
 use crate::parser::core::DataParser;
 use crate::utils::ParseResult;
 
+/// Runs `parser`, restoring the cursor if it fails.
+pub(crate) fn backtrack<T, P>(input: &mut DataParser, parser: P) -> ParseResult<T>
+where
+    P: FnOnce(&mut DataParser) -> ParseResult<T>,
+{
+    let checkpoint = input.checkpoint();
+    let result = parser(input);
+    if result.is_err() {
+        input.restore(checkpoint);
+    }
+    result
+}
+
+/// A tuple of parsers that all produce `T`, tried in order by [`alt`].
+///
+/// Implemented for tuples of up to 8 parsers.
+pub trait Alt<T> {
+    /// Returns the result of the first parser that succeeds.
+    fn choice(&self, input: &mut DataParser) -> ParseResult<T>;
+}
+
+/// A tuple of parsers run one after another by [`tuple()`].
+///
+/// Implemented for tuples of up to 8 parsers; the output is the tuple of their results.
+pub trait Sequence<T> {
+    /// Runs each parser in order and collects their results.
+    fn sequence(&self, input: &mut DataParser) -> ParseResult<T>;
+}
+
+impl_combinator_tuple!(A OA 0);
+impl_combinator_tuple!(A OA 0, B OB 1);
+impl_combinator_tuple!(A OA 0, B OB 1, C OC 2);
+impl_combinator_tuple!(A OA 0, B OB 1, C OC 2, D OD 3);
+impl_combinator_tuple!(A OA 0, B OB 1, C OC 2, D OD 3, E OE 4);
+impl_combinator_tuple!(A OA 0, B OB 1, C OC 2, D OD 3, E OE 4, F OF 5);
+impl_combinator_tuple!(A OA 0, B OB 1, C OC 2, D OD 3, E OE 4, F OF 5, G OG 6);
+impl_combinator_tuple!(A OA 0, B OB 1, C OC 2, D OD 3, E OE 4, F OF 5, G OG 6, H OH 7);
+
 /// Matches and consumes an exact tag (byte sequence) from the input buffer.
 ///
 /// # Arguments
@@ -21,14 +87,15 @@ pub fn delim_extract(
     expected: &'static [u8],
 ) -> impl for<'a> Fn(&'a mut DataParser<'a>) -> ParseResult<&'a [u8]> {
     move |parser: &mut DataParser| {
-        let actual = parser.take(expected.len())?;
-        if actual == expected {
-            Ok(actual)
-        } else {
-            Err(DataParseError::Custom {
-                e: format!("Tag mismatch: expected {:?}, got {:?}", expected, actual),
-            })
+        let actual = parser.peek(expected.len())?;
+        if actual != expected {
+            return Err(DataParseError::Mismatch {
+                expected: format!("tag {:?}", expected),
+                actual: format!("{:?}", actual),
+            }
+            .at(parser.position()));
         }
+        parser.take(expected.len())
     }
 }
 
@@ -45,7 +112,7 @@ where
     F: Fn(&mut DataParser) -> ParseResult<A>,
     G: Fn(A) -> B,
 {
-    move |p: &mut DataParser| parser(p).map(&f)
+    move |p: &mut DataParser| backtrack(p, &parser).map(&f)
 }
 
 /// Parses a value surrounded by specific start and end delimiters.
@@ -69,44 +136,517 @@ where
     P: Fn(&mut DataParser) -> ParseResult<T>,
 {
     move |input: &mut DataParser| {
-        let start = input.get_byte()?;
-        if start != delim_start {
-            return Err(DataParseError::Custom {
-                e: format!(
-                    "Expected start delimiter {:?}, found {:?}",
-                    delim_start, start
-                ),
-            });
+        backtrack(input, |input| {
+            let start = input.get_byte()?;
+            if start != delim_start {
+                return Err(DataParseError::Mismatch {
+                    expected: format!("start delimiter {:?}", delim_start),
+                    actual: format!("{:?}", start),
+                }
+                .at(input.position() - 1));
+            }
+            let value = parser(input)?;
+            let end = input.get_byte()?;
+            if end != delim_end {
+                return Err(DataParseError::Mismatch {
+                    expected: format!("end delimiter {:?}", delim_end),
+                    actual: format!("{:?}", end),
+                }
+                .at(input.position() - 1));
+            }
+
+            Ok(value)
+        })
+    }
+}
+
+/// Labels the errors of `parser` with `label`.
+///
+/// Nested contexts stack up, outermost first, and the error keeps the offset it occurred
+/// at (or, for errors that don't carry one, the offset `parser` failed at).
+///
+/// # Example
+/// ```
+/// use dataparser_core::DataParser;
+/// use dataparser_core::parser::combinators::context;
+/// let header = context("header", context("length", |p: &mut DataParser| p.get_u32()));
+/// let mut parser = DataParser::new(&[0x00, 0x00]);
+/// let error = header(&mut parser).unwrap_err();
+/// assert_eq!(error.offset(), Some(0));
+/// assert_eq!(error.to_string(), "at 0x0 in header.length: needed 4 bytes, 2 available");
+/// ```
+pub fn context<T, P>(label: &'static str, parser: P) -> impl Fn(&mut DataParser) -> ParseResult<T>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| {
+        let checkpoint = input.checkpoint();
+        parser(input).map_err(|e| {
+            let offset = input.position();
+            input.restore(checkpoint);
+            e.with_context(label, offset)
+        })
+    }
+}
+
+/// Tries each parser in `parsers` in turn and returns the first success.
+///
+/// Every failed branch is rolled back before the next one runs. If all branches fail, the
+/// error of the last one is returned.
+///
+/// # Example
+/// ```
+/// use dataparser_core::DataParser;
+/// use dataparser_core::parser::combinators::{alt, map};
+/// let mut parser = DataParser::new(&[0x00, 0x2A]);
+/// let value = alt((
+///     map(|p: &mut DataParser| p.get_u32(), u64::from),
+///     map(|p: &mut DataParser| p.get_u16(), u64::from),
+/// ))(&mut parser)?;
+/// assert_eq!(value, 42);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub fn alt<T, L>(parsers: L) -> impl Fn(&mut DataParser) -> ParseResult<T>
+where
+    L: Alt<T>,
+{
+    move |input: &mut DataParser| parsers.choice(input)
+}
+
+/// What [`dispatch`] does with a tag that none of its branches handles.
+pub enum UnknownTag<K, T> {
+    /// Fail with a [`DataParseError::Mismatch`] naming the tag, leaving the cursor untouched.
+    Error,
+    /// Keep the raw tag by turning it into a value, e.g. an `Unknown(tag)` variant.
+    Preserve(fn(K) -> T),
+}
+
+/// Reads a tag with `tag`, then lets `select` parse the value it stands for.
+///
+/// The tag parser can read anything that identifies a variant: an integer of any width,
+/// a varint, or a run of magic bytes. `select` receives the tag and the parser positioned
+/// right after it, and returns `None` when it doesn't know the tag, in which case `unknown`
+/// decides the outcome.
+///
+/// # Example
+/// ```
+/// use dataparser_core::{DataParseError, DataParser};
+/// use dataparser_core::parser::combinators::{UnknownTag, dispatch};
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Message {
+///     Ping(u32),
+///     Text(String),
+///     Unknown(u8),
+/// }
+///
+/// let message = dispatch(
+///     |p: &mut DataParser| p.get_u8(),
+///     |tag, p| match tag {
+///         1 => Some(p.get_u32().map(Message::Ping)),
+///         2 => Some(p.get_string(false).map(Message::Text)),
+///         _ => None,
+///     },
+///     UnknownTag::Preserve(Message::Unknown),
+/// );
+/// let mut parser = DataParser::new(&[0x01, 0x00, 0x00, 0x00, 0x2A, 0x09]);
+/// assert_eq!(message(&mut parser)?, Message::Ping(42));
+/// assert_eq!(message(&mut parser)?, Message::Unknown(9));
+///
+/// // With `UnknownTag::Error`, the error points at the unknown tag.
+/// let ping = dispatch(
+///     |p: &mut DataParser| p.get_u8(),
+///     |tag, p| (*tag == 1).then(|| p.get_u8()),
+///     UnknownTag::Error,
+/// );
+/// let mut parser = DataParser::new(&[0x01, 0x07, 0x09]);
+/// assert_eq!(ping(&mut parser)?, 7);
+/// let error = ping(&mut parser).unwrap_err();
+/// assert_eq!(error.offset(), Some(2));
+/// assert!(matches!(error.kind(), DataParseError::Mismatch { actual, .. } if actual == "9"));
+/// assert_eq!(parser.position(), 2);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub fn dispatch<K, T, P, F>(
+    tag: P,
+    select: F,
+    unknown: UnknownTag<K, T>,
+) -> impl Fn(&mut DataParser) -> ParseResult<T>
+where
+    K: Debug,
+    P: Fn(&mut DataParser) -> ParseResult<K>,
+    F: Fn(&K, &mut DataParser) -> Option<ParseResult<T>>,
+{
+    move |input: &mut DataParser| {
+        backtrack(input, |input| {
+            let start = input.position();
+            let key = tag(input)?;
+            if let Some(result) = select(&key, input) {
+                return result;
+            }
+            match unknown {
+                UnknownTag::Error => Err(DataParseError::Mismatch {
+                    expected: "a known tag".into(),
+                    actual: format!("{:?}", key),
+                }
+                .at(start)),
+                UnknownTag::Preserve(preserve) => Ok(preserve(key)),
+            }
+        })
+    }
+}
+
+/// Makes a parser optional.
+///
+/// Returns `Some` if `parser` succeeds, or `None` (with the cursor untouched) if it fails.
+pub fn opt<T, P>(parser: P) -> impl Fn(&mut DataParser) -> ParseResult<Option<T>>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| Ok(backtrack(input, &parser).ok())
+}
+
+/// Applies `parser` until it fails, collecting the results.
+///
+/// The failing attempt is rolled back, so the cursor ends right after the last match.
+///
+/// # Errors
+/// Returns an error if `parser` succeeds without consuming any input, since it would
+/// otherwise match forever.
+pub fn many0<T, P>(parser: P) -> impl Fn(&mut DataParser) -> ParseResult<Vec<T>>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| {
+        let mut values = Vec::new();
+        loop {
+            let start = input.position();
+            match backtrack(input, &parser) {
+                Ok(_) if input.position() == start => {
+                    return Err(DataParseError::Custom {
+                        e: "many parser succeeded without consuming input".into(),
+                    });
+                }
+                Ok(value) => values.push(value),
+                Err(_) => return Ok(values),
+            }
+        }
+    }
+}
+
+/// Like [`many0`], but `parser` has to match at least once.
+///
+/// # Errors
+/// Returns the error of the first attempt if it fails.
+pub fn many1<T, P>(parser: P) -> impl Fn(&mut DataParser) -> ParseResult<Vec<T>>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    let many = many0(parser);
+    move |input: &mut DataParser| {
+        backtrack(input, |input| {
+            let values = many(input)?;
+            if values.is_empty() {
+                return Err(DataParseError::Custom {
+                    e: "many1 parser did not match".into(),
+                });
+            }
+            Ok(values)
+        })
+    }
+}
+
+/// Applies `parser` exactly `n` times.
+///
+/// # Errors
+/// Returns the first error; the cursor is then restored to before the first item.
+pub fn count<T, P>(parser: P, n: usize) -> impl Fn(&mut DataParser) -> ParseResult<Vec<T>>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| {
+        backtrack(input, |input| {
+            let mut values = Vec::with_capacity(n.min(input.remaining()));
+            for _ in 0..n {
+                values.push(parser(input)?);
+            }
+            Ok(values)
+        })
+    }
+}
+
+/// Parses zero or more `element`s separated by `separator`.
+///
+/// A trailing separator that isn't followed by an element is left unconsumed.
+///
+/// # Example
+/// ```
+/// use dataparser_core::DataParser;
+/// use dataparser_core::parser::combinators::separated_list;
+/// let mut parser = DataParser::new(b"a,b,c;");
+/// let items = separated_list(
+///     |p: &mut DataParser| p.get_byte().map(|_| ()),
+///     |p: &mut DataParser| p.get_byte(),
+/// );
+/// assert_eq!(items(&mut parser)?, vec![b'a', b'b', b'c']);
+/// assert_eq!(parser.get_byte()?, b';');
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub fn separated_list<S, T, P, E>(
+    separator: P,
+    element: E,
+) -> impl Fn(&mut DataParser) -> ParseResult<Vec<T>>
+where
+    P: Fn(&mut DataParser) -> ParseResult<S>,
+    E: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| {
+        let mut values = Vec::new();
+        match backtrack(input, &element) {
+            Ok(value) => values.push(value),
+            Err(_) => return Ok(values),
         }
-        let value = parser(input)?;
-        let end = input.get_byte()?;
-        if end != delim_end {
-            return Err(DataParseError::Custom {
-                e: format!("Expected end delimiter {:?}, found {:?}", delim_end, end),
+        loop {
+            let start = input.position();
+            let next = backtrack(input, |input| {
+                separator(input)?;
+                element(input)
             });
+            match next {
+                Ok(_) if input.position() == start => {
+                    return Err(DataParseError::Custom {
+                        e: "separated_list parsers succeeded without consuming input".into(),
+                    });
+                }
+                Ok(value) => values.push(value),
+                Err(_) => return Ok(values),
+            }
         }
+    }
+}
 
-        Ok(value)
+/// Runs `first` then `second`, returning only the result of `second`.
+pub fn preceded<A, B, P, Q>(first: P, second: Q) -> impl Fn(&mut DataParser) -> ParseResult<B>
+where
+    P: Fn(&mut DataParser) -> ParseResult<A>,
+    Q: Fn(&mut DataParser) -> ParseResult<B>,
+{
+    move |input: &mut DataParser| {
+        backtrack(input, |input| {
+            first(input)?;
+            second(input)
+        })
     }
 }
 
-impl DataParser<'_> {
+/// Runs `first` then `second`, returning only the result of `first`.
+pub fn terminated<A, B, P, Q>(first: P, second: Q) -> impl Fn(&mut DataParser) -> ParseResult<A>
+where
+    P: Fn(&mut DataParser) -> ParseResult<A>,
+    Q: Fn(&mut DataParser) -> ParseResult<B>,
+{
+    move |input: &mut DataParser| {
+        backtrack(input, |input| {
+            let value = first(input)?;
+            second(input)?;
+            Ok(value)
+        })
+    }
+}
+
+/// Runs each parser in `parsers` in order and returns their results as a tuple.
+///
+/// # Example
+/// ```
+/// use dataparser_core::DataParser;
+/// use dataparser_core::parser::combinators::tuple;
+/// let mut parser = DataParser::new(&[0x01, 0x00, 0x02, 0x03]);
+/// let (a, b, c) = tuple((
+///     |p: &mut DataParser| p.get_u8(),
+///     |p: &mut DataParser| p.get_u16(),
+///     |p: &mut DataParser| p.get_bool(),
+/// ))(&mut parser)?;
+/// assert_eq!((a, b, c), (1, 2, true));
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub fn tuple<T, L>(parsers: L) -> impl Fn(&mut DataParser) -> ParseResult<T>
+where
+    L: Sequence<T>,
+{
+    move |input: &mut DataParser| backtrack(input, |input| parsers.sequence(input))
+}
+
+/// Runs `parser` and checks its result against `predicate`.
+///
+/// # Errors
+/// Returns an error (and restores the cursor) if `parser` fails or `predicate` returns `false`.
+pub fn verify<T, P, F>(parser: P, predicate: F) -> impl Fn(&mut DataParser) -> ParseResult<T>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+    F: Fn(&T) -> bool,
+{
+    move |input: &mut DataParser| {
+        backtrack(input, |input| {
+            let start = input.position();
+            let value = parser(input)?;
+            if !predicate(&value) {
+                return Err(DataParseError::Custom {
+                    e: "Parsed value failed verification".into(),
+                }
+                .at(start));
+            }
+            Ok(value)
+        })
+    }
+}
+
+/// Runs `parser` only if `condition` is `true`; otherwise returns `None` without reading.
+pub fn cond<T, P>(condition: bool, parser: P) -> impl Fn(&mut DataParser) -> ParseResult<Option<T>>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| {
+        if condition {
+            backtrack(input, &parser).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Consumes bytes for as long as `predicate` returns `true`, possibly none.
+///
+/// Stops at the first non-matching byte (which is not consumed) or at the end of input.
+///
+/// # Errors
+/// The returned bytes are copied, so they count against `max_alloc`; going over it fails
+/// with [`DataParseError::LimitExceeded`] without consuming anything.
+///
+/// # Example
+/// ```
+/// use dataparser_core::{DataParseError, DataParser, ParseOptions};
+/// use dataparser_core::parser::combinators::take_while;
+/// let mut parser = DataParser::new(b"123abc");
+/// assert_eq!(take_while(|b: u8| b.is_ascii_digit())(&mut parser)?, b"123");
+///
+/// let mut parser = DataParser::with_options(b"123abc", ParseOptions::default().with_max_alloc(2));
+/// assert!(matches!(
+///     take_while(|b: u8| b.is_ascii_digit())(&mut parser).unwrap_err().kind(),
+///     DataParseError::LimitExceeded { .. }
+/// ));
+/// assert_eq!(parser.position(), 0);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub fn take_while<F>(predicate: F) -> impl Fn(&mut DataParser) -> ParseResult<Vec<u8>>
+where
+    F: Fn(u8) -> bool,
+{
+    move |input: &mut DataParser| {
+        let rest = input.peek(input.remaining())?;
+        let len = rest
+            .iter()
+            .position(|&byte| !predicate(byte))
+            .unwrap_or(rest.len());
+        input.reserve(len)?;
+        Ok(input.take(len)?.to_vec())
+    }
+}
+
+/// Consumes bytes until `predicate` returns `true`, possibly none.
+///
+/// The matching byte is not consumed. Unlike [`DataParser::parse_until`], reaching the end
+/// of input without a match is not an error.
+///
+/// # Errors
+/// Like [`take_while`], fails if the copied bytes go over `max_alloc`.
+pub fn take_till<F>(predicate: F) -> impl Fn(&mut DataParser) -> ParseResult<Vec<u8>>
+where
+    F: Fn(u8) -> bool,
+{
+    take_while(move |byte| !predicate(byte))
+}
+
+/// Runs `parser` without consuming any input, whether it succeeds or not.
+pub fn peek<T, P>(parser: P) -> impl Fn(&mut DataParser) -> ParseResult<T>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| {
+        let checkpoint = input.checkpoint();
+        let result = parser(input);
+        input.restore(checkpoint);
+        result
+    }
+}
+
+/// Runs `parser` and returns the bytes it consumed instead of its result.
+///
+/// The bytes are copied and count against `max_alloc`; if that limit is exceeded, nothing
+/// is consumed.
+///
+/// # Example
+/// ```
+/// use dataparser_core::DataParser;
+/// use dataparser_core::parser::combinators::recognize;
+/// let mut parser = DataParser::new(&[0x00, 0x01, 0xFF]);
+/// let raw = recognize(|p: &mut DataParser| p.get_u16())(&mut parser)?;
+/// assert_eq!(raw, vec![0x00, 0x01]);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub fn recognize<T, P>(parser: P) -> impl Fn(&mut DataParser) -> ParseResult<Vec<u8>>
+where
+    P: Fn(&mut DataParser) -> ParseResult<T>,
+{
+    move |input: &mut DataParser| {
+        let start = input.checkpoint();
+        backtrack(input, &parser)?;
+        let len = input.position() - start.position();
+        input.restore(start);
+        input.reserve(len)?;
+        Ok(input.take(len)?.to_vec())
+    }
+}
+
+impl<'a> DataParser<'a> {
     /// Parses a value from a length-prefixed sub-buffer.
     ///
-    /// Reads a `u32` length, then creates a sub-parser scoped to the slice of that length.
-    /// Passes the sub-parser to the provided closure.
+    /// Reads a length using the configured length prefix, then creates a sub-parser scoped
+    /// to the slice of that length. Passes the sub-parser to the provided closure.
     ///
     /// Useful for safely parsing encapsulated structures like compressed, encrypted,
     /// or nested payloads.
     pub(crate) fn parse_with_length_prefix<T, F>(&mut self, f: F) -> ParseResult<T>
     where
-        F: FnOnce(&mut DataParser) -> ParseResult<T>,
+        F: FnOnce(&mut DataParser<'a>) -> ParseResult<T>,
     {
-        let options = self.options.clone();
-        let len = self.__get_u32()?;
-        let mut sub_buffer = self.take(len as usize)?.to_vec();
-        let mut sub_parser = DataParser::with_options(&mut sub_buffer, options);
-        f(&mut sub_parser)
+        self.parse_with_prefix(self.options.length_prefix, f)
+    }
+
+    /// Parses a value from a sub-buffer whose length is encoded as `prefix`.
+    ///
+    /// Works like the length-prefixed sections used for `length_prefixed_fields`, but with
+    /// an explicit prefix kind. The closure receives a sub-parser that can't read past the
+    /// end of the section. The sub-parser borrows the same input as this parser, so zero-copy
+    /// reads inside the section (e.g. [`get_slice`](DataParser::get_slice)) return data
+    /// borrowed for `'a`.
+    ///
+    /// # Example
+    /// ```
+    /// use dataparser_core::{DataParser, options::LengthPrefix};
+    /// let mut parser = DataParser::new(&[0x02, 0x00, 0x2A, 0x01, 0xFF, 0xEE]);
+    /// let value = parser.parse_with_prefix(LengthPrefix::U8, |section| section.get_u16())?;
+    /// assert_eq!(value, 42);
+    /// let raw: &[u8] = parser.parse_with_prefix(LengthPrefix::U8, |section| section.get_slice(1))?;
+    /// assert_eq!(raw, &[0xFF]);
+    /// assert_eq!(parser.get_u8()?, 0xEE);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    pub fn parse_with_prefix<T, F>(&mut self, prefix: LengthPrefix, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut DataParser<'a>) -> ParseResult<T>,
+    {
+        let len = self.__get_length(prefix)?;
+        self.parse_section(len, f)
     }
 
     /// Applies a parser function directly on this `DataParser`.
@@ -115,7 +655,12 @@ impl DataParser<'_> {
     ///
     /// # Example
     /// ```
+    /// # use dataparser_core::{DataParser, ParseResult};
+    /// # let mut parser = DataParser::new(&[0x01, 0x02]);
+    /// # fn my_parser_fn(p: &mut DataParser) -> ParseResult<u16> { p.get_u16() }
     /// let result = parser.parse_with(my_parser_fn)?;
+    /// # assert_eq!(result, 0x0102);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
     /// ```
     pub fn parse_with<T, P>(&mut self, parser: P) -> ParseResult<T>
     where
@@ -141,12 +686,12 @@ impl DataParser<'_> {
     {
         let mut collected = Vec::new();
         while self.remaining() > 0 {
-            if let Some(limit) = max_len {
-                if collected.len() >= limit {
-                    return Err(DataParseError::Custom {
-                        e: "parse_until exceeded max_len".into(),
-                    });
-                }
+            if let Some(limit) = max_len
+                && collected.len() >= limit
+            {
+                return Err(DataParseError::Custom {
+                    e: "parse_until exceeded max_len".into(),
+                });
             }
 
             let byte = self.get_byte()?;
@@ -157,7 +702,4 @@ impl DataParser<'_> {
         }
         Ok(collected)
     }
-    
-    
 }
-
@@ -1,6 +1,11 @@
+use std::io::SeekFrom;
+
 use crate::parser::buffer::Buffer;
 use crate::{
-    errors::DataParseError, impl_get_with_prefix, options::ParseOptions, utils::ParseResult,
+    errors::DataParseError,
+    impl_get_varint, impl_get_with_prefix,
+    options::{Limit, ParseOptions},
+    utils::{ParseResult, check_uint_width, sign_extend, uint_from_endian_bytes},
 };
 
 /// A configurable binary data parser that reads structured data from a byte buffer.
@@ -14,7 +19,7 @@ use crate::{
 ///
 /// ```rust
 /// use dataparser_core::parser::DataParser;
-/// let data = &[0x01, 0x00, 0x02];
+/// let data: &[u8] = &[0x01, 0x00, 0x02];
 /// let mut parser = DataParser::new(data);
 /// let first_byte = parser.get_byte().unwrap();
 /// assert_eq!(first_byte, 0x01);
@@ -29,6 +34,26 @@ pub struct DataParser<'a> {
 
     /// Parser configuration options.
     pub(crate) options: ParseOptions,
+
+    /// Current nesting depth, checked against `max_depth`.
+    pub(crate) depth: usize,
+
+    /// Bytes allocated for decoded data so far, checked against `max_alloc`.
+    pub(crate) allocated: usize,
+}
+
+/// A saved cursor position within a [`DataParser`].
+///
+/// Created with [`DataParser::checkpoint`] and handed back to [`DataParser::restore`]
+/// to rewind the parser, e.g. after a speculative parse of an optional section fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+impl Checkpoint {
+    /// Returns the cursor position captured by this checkpoint.
+    pub fn position(&self) -> usize {
+        self.0
+    }
 }
 impl<'a> DataParser<'a> {
     /// Creates a new `DataParser` from the given buffer and parsing options.
@@ -41,6 +66,8 @@ impl<'a> DataParser<'a> {
             buffer: buffer.into(),
             cursor: 0,
             options,
+            depth: 0,
+            allocated: 0,
         }
     }
 
@@ -56,18 +83,171 @@ impl<'a> DataParser<'a> {
     /// # Arguments
     /// * `buffer` - A byte buffer to read from.
     pub fn new<B: Into<Buffer<'a>>>(buffer: B) -> Self {
-        Self {
-            buffer: buffer.into(),
-            cursor: 0,
-            options: ParseOptions::default(),
-        }
+        Self::with_options(buffer, ParseOptions::default())
     }
 
     /// Returns the number of bytes remaining in the buffer.
-    pub(crate) fn remaining(&self) -> usize {
+    pub fn remaining(&self) -> usize {
         self.buffer.len().saturating_sub(self.cursor)
     }
 
+    /// Returns the current cursor position, as an offset from the start of the buffer.
+    pub fn position(&self) -> usize {
+        self.cursor
+    }
+
+    /// Moves the cursor to a new position and returns it.
+    ///
+    /// Works like [`std::io::Seek`], except that the cursor can't be placed past the end
+    /// of the buffer.
+    ///
+    /// # Errors
+    /// Returns an error if the target position is negative or beyond the end of the buffer.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::SeekFrom;
+    /// use dataparser_core::DataParser;
+    /// let mut parser = DataParser::new(&[0x00, 0x01, 0x02, 0x03]);
+    /// parser.seek(SeekFrom::End(-1)).unwrap();
+    /// assert_eq!(parser.get_u8().unwrap(), 0x03);
+    /// parser.seek(SeekFrom::Start(1)).unwrap();
+    /// assert_eq!(parser.get_u8().unwrap(), 0x01);
+    /// ```
+    pub fn seek(&mut self, pos: SeekFrom) -> ParseResult<usize> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(offset) => (0, i128::from(offset)),
+            SeekFrom::End(offset) => (self.buffer.len(), i128::from(offset)),
+            SeekFrom::Current(offset) => (self.cursor, i128::from(offset)),
+        };
+        let target = base as i128 + offset;
+        if target < 0 || target > self.buffer.len() as i128 {
+            return Err(DataParseError::Custom {
+                e: format!(
+                    "Seek to offset {} is outside of buffer bounds (length {})",
+                    target,
+                    self.buffer.len()
+                ),
+            });
+        }
+        self.cursor = target as usize;
+        Ok(self.cursor)
+    }
+
+    /// Advances the cursor by `n` bytes without reading them.
+    ///
+    /// # Errors
+    /// Returns an error if fewer than `n` bytes remain.
+    pub fn skip(&mut self, n: usize) -> ParseResult<()> {
+        self.take(n)?;
+        Ok(())
+    }
+
+    /// Captures the current cursor position so it can be restored later.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.cursor)
+    }
+
+    /// Rewinds (or advances) the cursor to a previously captured [`Checkpoint`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::DataParser;
+    /// let mut parser = DataParser::new(&[0x00, 0x2A]);
+    /// let checkpoint = parser.checkpoint();
+    /// assert_eq!(parser.get_u16().unwrap(), 0x2A);
+    /// parser.restore(checkpoint);
+    /// assert_eq!(parser.get_u8().unwrap(), 0x00);
+    /// ```
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        self.cursor = checkpoint.0.min(self.buffer.len());
+    }
+
+    /// Runs `f` one nesting level deeper.
+    ///
+    /// Sections, collection elements and optional values are nested automatically; call
+    /// this from recursive [`Decodable`] implementations (e.g. trees) so that
+    /// [`max_depth`] also bounds them.
+    ///
+    /// # Errors
+    /// Returns [`DataParseError::LimitExceeded`] if `max_depth` would be exceeded, or the
+    /// error of `f`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{DataParser, ParseOptions};
+    /// let mut parser = DataParser::with_options(&[0x01], ParseOptions::default().with_max_depth(1));
+    /// assert!(parser.nested(|p| p.nested(|p| p.get_u8())).is_err());
+    /// assert_eq!(parser.nested(|p| p.get_u8()).unwrap(), 1);
+    /// ```
+    ///
+    /// [`Decodable`]: crate::Decodable
+    /// [`max_depth`]: crate::options::ParseOptions::set_max_depth
+    pub fn nested<T, F>(&mut self, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut Self) -> ParseResult<T>,
+    {
+        self.check_limit(Limit::Depth, self.depth + 1)?;
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Checks `value` against a limit of the parser options, locating the error at the cursor.
+    pub(crate) fn check_limit(&self, limit: Limit, value: usize) -> ParseResult<()> {
+        self.options
+            .check_limit(limit, value)
+            .map_err(|e| e.at(self.cursor))
+    }
+
+    /// Accounts for `n` bytes about to be allocated for decoded data.
+    ///
+    /// # Errors
+    /// Returns an error if this would exceed `max_alloc`.
+    pub(crate) fn reserve(&mut self, n: usize) -> ParseResult<()> {
+        let total = self.allocated.saturating_add(n);
+        self.check_limit(Limit::Allocation, total)?;
+        self.allocated = total;
+        Ok(())
+    }
+
+    /// Runs `f` on a sub-parser over the next `len` bytes, one nesting level deeper.
+    ///
+    /// The sub-parser shares the depth and allocation accounting of this parser. If this
+    /// parser borrows its input, so does the sub-parser, so zero-copy reads inside the
+    /// section still borrow for `'a`; only an owned section is copied, and counted against
+    /// `max_alloc`. Errors are located relative to this parser's input,
+    /// and running out of bytes inside the section is reported as a
+    /// [`DataParseError::SectionOverrun`].
+    pub(crate) fn parse_section<T, F>(&mut self, len: usize, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut DataParser<'a>) -> ParseResult<T>,
+    {
+        self.nested(|this| {
+            let start = this.position();
+            let borrowed = this.buffer.as_shared().is_some();
+            if !borrowed {
+                this.reserve(len)?;
+            }
+            if this.remaining() < len {
+                return Err(this.not_enough_bytes(len));
+            }
+            let end = start + len;
+            let sub_buffer = match this.buffer.as_shared() {
+                Some(data) => Buffer::Shared(&data[start..end]),
+                None => Buffer::from(this.buffer[start..end].to_vec()),
+            };
+            this.cursor = end;
+            let mut sub_parser = DataParser::with_options(sub_buffer, this.options.clone());
+            sub_parser.depth = this.depth;
+            sub_parser.allocated = this.allocated;
+            let result = f(&mut sub_parser);
+            this.allocated = sub_parser.allocated;
+            result.map_err(|e| e.in_section().offset_by(start))
+        })
+    }
+
     /// Consumes and returns the next `n` bytes from the buffer.
     ///
     /// Advances the internal cursor.
@@ -76,26 +256,7 @@ impl<'a> DataParser<'a> {
     /// Returns an error if there are not enough bytes remaining.
     pub(crate) fn take(&mut self, n: usize) -> ParseResult<&[u8]> {
         if self.remaining() < n {
-            return if self.options.verbose_errors {
-                let remaining_bytes = &self.buffer[self.cursor..];
-                eprintln!(
-                    "Failed to take {} bytes at offset {} — only {} bytes left: {:?}",
-                    n,
-                    self.cursor,
-                    self.remaining(),
-                    remaining_bytes
-                );
-                Err(DataParseError::Custom {
-                    e: format!(
-                        "Not enough bytes at offset {} (needed {}, have {})",
-                        self.cursor,
-                        n,
-                        self.remaining()
-                    ),
-                })
-            } else {
-                Err(DataParseError::UnexpectedEOF)
-            };
+            return Err(self.not_enough_bytes(n));
         }
 
         let start = self.cursor;
@@ -104,6 +265,15 @@ impl<'a> DataParser<'a> {
         Ok(&self.buffer[start..end])
     }
 
+    /// Builds the error for a read of `needed` bytes at the cursor that runs past the end.
+    pub(crate) fn not_enough_bytes(&self, needed: usize) -> DataParseError {
+        DataParseError::Incomplete {
+            needed,
+            available: self.remaining(),
+        }
+        .at(self.cursor)
+    }
+
     /// Reads exactly `N` bytes into a fixed-size array.
     ///
     /// # Errors
@@ -115,6 +285,28 @@ impl<'a> DataParser<'a> {
         Ok(array)
     }
 
+    /// Copies exactly `N` bytes starting at the absolute `offset` into a fixed-size array.
+    ///
+    /// Does not move the cursor.
+    ///
+    /// # Errors
+    /// Returns an error if the range `offset..offset + N` is outside the buffer.
+    pub(crate) fn read_array_at<const N: usize>(&self, offset: usize) -> ParseResult<[u8; N]> {
+        let slice = offset
+            .checked_add(N)
+            .and_then(|end| self.buffer.get(offset..end))
+            .ok_or_else(|| {
+                DataParseError::Incomplete {
+                    needed: N,
+                    available: self.buffer.len().saturating_sub(offset),
+                }
+                .at(offset)
+            })?;
+        let mut array = [0u8; N];
+        array.copy_from_slice(slice);
+        Ok(array)
+    }
+
     /// Returns the total length of the underlying buffer.
     pub fn current_len(&self) -> usize {
         self.buffer.len()
@@ -122,13 +314,27 @@ impl<'a> DataParser<'a> {
 
     /// Peeks at the next `n` bytes in the buffer without advancing the cursor.
     ///
+    /// Typed variants (`peek_u8`, `peek_u16`, ...) and absolute-offset reads
+    /// (`get_u8_at`, `get_u32_at`, ...) are also available and honor the configured endianness.
+    ///
     /// # Errors
     /// Returns an error if not enough bytes are available.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::DataParser;
+    /// let mut parser = DataParser::new(&[0x01, 0x00, 0x02, 0x00, 0x00, 0x00, 0x07]);
+    /// parser.skip(1).unwrap();
+    /// assert_eq!(parser.peek(2).unwrap(), &[0x00, 0x02]);
+    /// assert_eq!(parser.peek_u16().unwrap(), 0x0002);
+    /// assert_eq!(parser.get_u32_at(3).unwrap(), 7);
+    /// assert_eq!(parser.position(), 1);
+    /// ```
     pub fn peek(&self, n: usize) -> ParseResult<&[u8]> {
         if self.remaining() < n {
-            return Err(DataParseError::UnexpectedEOF);
+            return Err(self.not_enough_bytes(n));
         }
-        Ok(&self.buffer[..n])
+        Ok(&self.buffer[self.cursor..self.cursor + n])
     }
 
     /// Reads the next `byte_len` bytes and returns them as a `Vec<u8>`.
@@ -136,15 +342,62 @@ impl<'a> DataParser<'a> {
     /// Advances the internal cursor.
     ///
     /// # Errors
-    /// Returns an error if not enough bytes are available.
+    /// Returns an error if not enough bytes are available. A length over the allocation
+    /// limit fails with [`DataParseError::LimitExceeded`] even if the input is shorter, so
+    /// incremental decoders don't wait for bytes they would reject anyway.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{DataParseError, DataParser, ParseOptions};
+    /// let options = ParseOptions::default().with_max_alloc(64);
+    /// let mut parser = DataParser::with_options(&[0x01, 0x02], options);
+    /// assert!(matches!(
+    ///     parser.get_bytes(0xFFFF_FF00).unwrap_err().kind(),
+    ///     DataParseError::LimitExceeded { .. }
+    /// ));
+    /// ```
     pub fn get_bytes(&mut self, byte_len: usize) -> ParseResult<Vec<u8>> {
-        if self.remaining() < byte_len {
-            return Err(DataParseError::UnexpectedEOF);
-        }
+        self.reserve(byte_len)?;
         let buf = self.take(byte_len)?.to_vec();
         Ok(buf)
     }
 
+    /// Reads the next `n` bytes and returns them as a slice borrowed from the input.
+    ///
+    /// Unlike [`get_bytes`], this does not allocate, and the returned slice lives as long as
+    /// the input buffer (`'a`) rather than the parser borrow, so it can be stored in decoded values.
+    ///
+    /// # Errors
+    /// Returns an error if not enough bytes are available, or if the parser owns its buffer
+    /// (e.g. it was created from a `Vec<u8>`), since there is no input to borrow from.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::parser::DataParser;
+    /// let data: &[u8] = &[0xDE, 0xAD, 0xBE, 0xEF];
+    /// let mut parser = DataParser::new(data);
+    /// let head = parser.get_slice(2).unwrap();
+    /// let tail = parser.get_slice(2).unwrap();
+    /// assert_eq!(head, &[0xDE, 0xAD]);
+    /// assert_eq!(tail, &[0xBE, 0xEF]);
+    /// ```
+    ///
+    /// [`get_bytes`]: DataParser::get_bytes
+    pub fn get_slice(&mut self, n: usize) -> ParseResult<&'a [u8]> {
+        if self.remaining() < n {
+            return Err(self.not_enough_bytes(n));
+        }
+        let data = self
+            .buffer
+            .as_shared()
+            .ok_or_else(|| DataParseError::Custom {
+                e: "Zero-copy reads require a borrowed input buffer".into(),
+            })?;
+        let start = self.cursor;
+        self.cursor += n;
+        Ok(&data[start..self.cursor])
+    }
+
     /// Reads a single byte from the buffer.
     ///
     /// # Errors
@@ -163,5 +416,46 @@ impl<'a> DataParser<'a> {
         Ok(self.get_byte()? != 0)
     }
 
-    impl_get_with_prefix!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+    /// Reads an unsigned integer that is `n_bytes` wide (1 to 8), using the configured endianness.
+    ///
+    /// Useful for fields that don't map onto a Rust primitive, such as 24- or 48-bit integers.
+    ///
+    /// # Errors
+    /// Returns an error if `n_bytes` is out of range or not enough bytes are available.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::DataParser;
+    /// let mut parser = DataParser::new(&[0x01, 0x02, 0x03, 0xFF, 0xFF, 0xFE]);
+    /// assert_eq!(parser.get_uint(3).unwrap(), 0x010203);
+    /// assert_eq!(parser.get_int(3).unwrap(), -2);
+    /// ```
+    pub fn get_uint(&mut self, n_bytes: usize) -> ParseResult<u64> {
+        check_uint_width(n_bytes)?;
+        let endianness = self.options.endianness.clone();
+        let bytes = self.take(n_bytes)?;
+        uint_from_endian_bytes(bytes, &endianness)
+    }
+
+    /// Reads a two's complement signed integer that is `n_bytes` wide (1 to 8), using the
+    /// configured endianness, and sign-extends it to an `i64`.
+    ///
+    /// # Errors
+    /// Returns an error if `n_bytes` is out of range or not enough bytes are available.
+    pub fn get_int(&mut self, n_bytes: usize) -> ParseResult<i64> {
+        Ok(sign_extend(self.get_uint(n_bytes)?, n_bytes))
+    }
+
+    impl_get_with_prefix!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+    );
+
+    // `peek_*` reads at the cursor and `get_*_at` reads at an absolute offset, both without
+    // moving the cursor. These always read the raw value and ignore `length_prefixed_fields`.
+    impl_get_with_prefix!(@random_access u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+    // `get_leb128_*` reads unsigned LEB128 for unsigned types and signed LEB128 for signed
+    // types, `get_zigzag_*` reads zigzag-encoded LEB128. Over-long encodings are rejected.
+    impl_get_varint!(@unsigned u8, u16, u32, u64, u128, usize);
+    impl_get_varint!(@signed i8, i16, i32, i64, i128, isize);
 }
@@ -0,0 +1,190 @@
+use std::marker::PhantomData;
+
+use crate::{
+    options::{Limit, ParseOptions},
+    parser::{core::DataParser, helpers::Decodable},
+    utils::ParseResult,
+};
+
+/// Default upper bound on the bytes a [`FrameDecoder`] buffers for a single value.
+pub const DEFAULT_MAX_BUFFERED: usize = 8 * 1024 * 1024;
+
+/// An incremental decoder for values that arrive in arbitrary chunks.
+///
+/// Bytes are pushed in with [`feed`] as they arrive (e.g. from a non-blocking socket),
+/// and complete values are pulled out with [`next_frame`] or [`frames`]. A value that is
+/// only partially received stays buffered until the rest of it has been fed.
+///
+/// When a decode attempt runs out of input, the decoder remembers how many bytes the failed
+/// read was missing and doesn't try again until at least that many have been fed, so small
+/// chunks don't cause the pending value to be re-parsed on every call. Each attempt still
+/// decodes the pending value from its first byte, though: a value made of many small reads
+/// (e.g. a long vector) that arrives a few bytes at a time is parsed once per missing read,
+/// which is quadratic in its size. Feeding larger chunks, or bounding values with
+/// [`ParseOptions::set_max_buffered`], keeps this in check.
+///
+/// # Example
+/// ```rust
+/// use dataparser_core::parser::frame::FrameDecoder;
+/// let mut decoder = FrameDecoder::<String>::new();
+/// decoder.feed(&[0x00, 0x00, 0x00, 0x02, b'h']);
+/// assert_eq!(decoder.next_frame()?, None);
+/// decoder.feed(&[b'i', 0x00, 0x00]);
+/// assert_eq!(decoder.next_frame()?, Some("hi".to_string()));
+/// assert_eq!(decoder.next_frame()?, None);
+/// assert_eq!(decoder.buffered(), 2);
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+///
+/// [`feed`]: FrameDecoder::feed
+/// [`next_frame`]: FrameDecoder::next_frame
+/// [`frames`]: FrameDecoder::frames
+pub struct FrameDecoder<T: Decodable> {
+    /// Bytes fed so far, including already decoded ones before `start`.
+    buffer: Vec<u8>,
+
+    /// Start of the first byte that hasn't been decoded yet.
+    start: usize,
+
+    /// Number of buffered bytes required before the next decode attempt.
+    required: usize,
+
+    /// Options used for every decode.
+    options: ParseOptions,
+
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Decodable> Default for FrameDecoder<T> {
+    fn default() -> Self {
+        Self::with_options(ParseOptions::default().with_max_buffered(DEFAULT_MAX_BUFFERED))
+    }
+}
+
+impl<T: Decodable> FrameDecoder<T> {
+    /// Creates a new `FrameDecoder` with default parsing options and a `max_buffered` of
+    /// [`DEFAULT_MAX_BUFFERED`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{DataParseError, parser::frame::FrameDecoder};
+    /// let mut decoder = FrameDecoder::<String>::new();
+    /// decoder.feed(&[0xff, 0xff, 0xff, 0xff, b'h']);
+    /// assert!(matches!(
+    ///     decoder.next_frame().unwrap_err(),
+    ///     DataParseError::LimitExceeded { .. }
+    /// ));
+    /// ```
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a new `FrameDecoder` that decodes with the given parsing options.
+    ///
+    /// The options are used as is, so `max_buffered` is only enforced if they set it.
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            buffer: Vec::new(),
+            start: 0,
+            required: 0,
+            options,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Updates the parsing options used for the next decodes.
+    pub fn set_options(&mut self, options: ParseOptions) {
+        self.options = options;
+        self.required = 0;
+    }
+
+    /// Appends a chunk of received bytes.
+    pub fn feed(&mut self, data: &[u8]) {
+        if self.start > 0 {
+            self.buffer.drain(..self.start);
+            self.start = 0;
+        }
+        self.buffer.extend_from_slice(data);
+    }
+
+    /// Returns the number of buffered bytes that haven't been decoded yet.
+    pub fn buffered(&self) -> usize {
+        self.buffer.len() - self.start
+    }
+
+    /// Returns `true` if no undecoded bytes are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.buffered() == 0
+    }
+
+    /// Discards all buffered bytes, e.g. to resynchronize after a malformed frame.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.start = 0;
+        self.required = 0;
+    }
+
+    /// Decodes the next value if it has been fully received.
+    ///
+    /// Returns `Ok(None)` if more bytes are needed. The bytes of a decoded value are removed
+    /// from the buffer; on `Ok(None)` or an error, the buffer is left untouched.
+    ///
+    /// # Errors
+    /// Returns an error if the buffered bytes are malformed; offsets in the error are relative
+    /// to the first undecoded byte. Since retrying won't help, the caller will usually
+    /// [`clear`](FrameDecoder::clear) the decoder or drop the connection.
+    ///
+    /// Also returns [`DataParseError::LimitExceeded`] if completing the pending value would
+    /// take more buffered bytes than [`ParseOptions::set_max_buffered`] allows.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{DataParseError, ParseOptions, parser::frame::FrameDecoder};
+    /// let options = ParseOptions::default().with_max_buffered(1024);
+    /// let mut decoder = FrameDecoder::<String>::with_options(options);
+    /// decoder.feed(&[0x7f, 0xff, 0xff, 0xff, b'h']);
+    /// assert!(matches!(
+    ///     decoder.next_frame().unwrap_err(),
+    ///     DataParseError::LimitExceeded { .. }
+    /// ));
+    /// ```
+    ///
+    /// [`DataParseError::LimitExceeded`]: crate::DataParseError::LimitExceeded
+    pub fn next_frame(&mut self) -> ParseResult<Option<T>> {
+        if self.buffered() == 0 || self.buffered() < self.required {
+            return Ok(None);
+        }
+        let mut parser = DataParser::with_options(&self.buffer[self.start..], self.options.clone());
+        match T::from_parser(&mut parser) {
+            Ok(value) => {
+                self.start += parser.position();
+                self.required = 0;
+                Ok(Some(value))
+            }
+            Err(e) => match e.missing_bytes() {
+                Some(missing) => {
+                    let required = self.buffered() + missing;
+                    self.options.check_limit(Limit::Buffered, required)?;
+                    self.required = required;
+                    Ok(None)
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    /// Returns an iterator over the values that can be decoded from the buffered bytes.
+    ///
+    /// The iterator ends once more bytes are needed, or after yielding an error.
+    pub fn frames(&mut self) -> impl Iterator<Item = ParseResult<T>> + '_ {
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let next = self.next_frame().transpose();
+            failed = matches!(next, Some(Err(_)));
+            next
+        })
+    }
+}
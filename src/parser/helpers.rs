@@ -1,21 +1,98 @@
 use super::core::DataParser;
-use crate::{errors::DataParseError, impl_deserializer, utils::ParseResult};
+use crate::{
+    errors::DataParseError,
+    options::{LengthPrefix, Limit, ParseOptions},
+    utils::ParseResult,
+};
 
 pub trait Decodable: Sized {
     fn from_parser(parser: &mut DataParser) -> ParseResult<Self>;
+
+    /// Decodes a value that takes up all of `bytes`.
+    ///
+    /// # Errors
+    /// Returns [`DataParseError::TrailingData`] if bytes are left over after the value. Use
+    /// [`decode_prefix`](Decodable::decode_prefix) to accept (and get back) trailing data.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{Decodable, ParseOptions};
+    /// let options = ParseOptions::default();
+    /// assert_eq!(u16::from_bytes(&[0x01, 0x02], &options)?, 0x0102);
+    /// assert!(u16::from_bytes(&[0x01, 0x02, 0x03], &options).is_err());
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    fn from_bytes(bytes: &[u8], options: &ParseOptions) -> ParseResult<Self> {
+        let (value, rest) = Self::decode_prefix(bytes, options)?;
+        if !rest.is_empty() {
+            return Err(
+                DataParseError::TrailingData { len: rest.len() }.at(bytes.len() - rest.len())
+            );
+        }
+        Ok(value)
+    }
+
+    /// Decodes a value from the start of `bytes` and returns it with the unconsumed remainder.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{Decodable, ParseOptions};
+    /// let (value, rest) = u16::decode_prefix(&[0x01, 0x02, 0x03], &ParseOptions::default())?;
+    /// assert_eq!(value, 0x0102);
+    /// assert_eq!(rest, &[0x03]);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    fn decode_prefix<'a>(bytes: &'a [u8], options: &ParseOptions) -> ParseResult<(Self, &'a [u8])> {
+        let mut parser = DataParser::with_options(bytes, options.clone());
+        let value = Self::from_parser(&mut parser)?;
+        Ok((value, &bytes[parser.position()..]))
+    }
 }
 
 impl DataParser<'_> {
+    /// Reads a length prefix of the given kind.
+    ///
+    /// Fixed-width prefixes use the configured prefix endianness. Like the numeric getters,
+    /// the prefix itself is wrapped in a length-prefixed field when `length_prefixed_fields` is set.
+    ///
+    /// # Errors
+    /// Returns an error if not enough bytes are available or the length doesn't fit in `usize`.
+    pub fn get_length(&mut self, prefix: LengthPrefix) -> ParseResult<usize> {
+        if self.options.length_prefixed_fields {
+            self.parse_with_length_prefix(|p| p.__get_length(prefix))
+        } else {
+            self.__get_length(prefix)
+        }
+    }
+
+    pub(crate) fn __get_length(&mut self, prefix: LengthPrefix) -> ParseResult<usize> {
+        let endianness = self.options.prefix_endianness().clone();
+        prefix.decode(&endianness, || self.get_byte())
+    }
+
     pub fn get_vector<T: Decodable>(&mut self) -> ParseResult<Vec<T>> {
-        let len = self.get_u32()? as usize;
-        let mut out = Vec::with_capacity(len);
-        let options = self.options.clone();
+        self.get_vector_with_prefix(self.options.length_prefix)
+    }
+
+    /// Reads a vector like [`get_vector`], using `prefix` for the element count and
+    /// element lengths instead of the configured length prefix.
+    ///
+    /// [`get_vector`]: DataParser::get_vector
+    pub fn get_vector_with_prefix<T: Decodable>(
+        &mut self,
+        prefix: LengthPrefix,
+    ) -> ParseResult<Vec<T>> {
+        let len = self.get_length(prefix)?;
+        self.check_limit(Limit::CollectionLength, len)?;
+        // Every element takes at least one byte, so the input bounds the capacity even when
+        // `len` is bogus.
+        let capacity = len.min(self.remaining());
+        self.reserve(capacity.saturating_mul(size_of::<T>()))?;
+        let mut out = Vec::with_capacity(capacity);
 
         for _ in 0..len {
-            let item_len = self.get_u32()? as usize;
-            let item_bytes = self.get_bytes(item_len)?;
-            let mut temp_parser = DataParser::with_options(item_bytes, options.clone());
-            out.push(T::from_parser(&mut temp_parser)?);
+            let item_len = self.get_length(prefix)?;
+            out.push(self.parse_section(item_len, T::from_parser)?);
         }
 
         Ok(out)
@@ -24,7 +101,7 @@ impl DataParser<'_> {
     pub fn get_option<T: Decodable>(&mut self) -> ParseResult<Option<T>> {
         let flag = self.get_bool()?;
         if flag {
-            Ok(Some(T::from_parser(self)?))
+            Ok(Some(self.nested(T::from_parser)?))
         } else {
             Ok(None)
         }
@@ -67,34 +144,12 @@ impl DataParser<'_> {
 
 impl<T: Decodable> Decodable for Option<T> {
     fn from_parser(parser: &mut DataParser) -> ParseResult<Self> {
-        let flag = parser.get_bool()?;
-        if flag {
-            Ok(Some(T::from_parser(parser)?))
-        } else {
-            Ok(None)
-        }
+        parser.get_option()
     }
 }
 
 impl<T: Decodable> Decodable for Vec<T> {
     fn from_parser(parser: &mut DataParser) -> ParseResult<Self> {
-        let len = parser.get_u32()?;
-        let mut out = Vec::with_capacity(len as usize);
-        let options = parser.options.clone();
-        for _ in 0..len {
-            let item_len = parser.get_u32()?;
-            let mut item_bytes = parser.take(item_len as usize)?.to_vec();
-            let mut temp_parser = DataParser::with_options(&mut item_bytes, options.clone());
-            out.push(T::from_parser(&mut temp_parser)?);
-        }
-        Ok(out)
-    }
-}
-
-impl Decodable for String {
-    fn from_parser(parser: &mut DataParser) -> ParseResult<Self> {
-        parser.get_string(false)
+        parser.get_vector()
     }
 }
-
-impl_deserializer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
@@ -1,5 +1,22 @@
 #[macro_export]
 macro_rules! impl_get_with_prefix {
+    (@random_access $($ty:ty),* $(,)?) => {
+        $(
+            paste::paste! {
+                pub fn [<peek_ $ty>](&self) -> $crate::utils::ParseResult<$ty> {
+                    self.[<get_ $ty _at>](self.cursor)
+                }
+                pub fn [<get_ $ty _at>](&self, offset: usize) -> $crate::utils::ParseResult<$ty> {
+                    let bytes = self.read_array_at::<{ std::mem::size_of::<$ty>() }>(offset)?;
+                    Ok(match self.options.endianness {
+                        $crate::utils::Endianness::BigEndian => <$ty>::from_be_bytes(bytes),
+                        $crate::utils::Endianness::LittleEndian => <$ty>::from_le_bytes(bytes),
+                        $crate::utils::Endianness::NativeEndian => <$ty>::from_ne_bytes(bytes),
+                    })
+                }
+            }
+        )*
+    };
     ($($ty:ty),* $(,)?) => {
         $(
             paste::paste! {
@@ -52,3 +69,209 @@ macro_rules! impl_stream_deserializer {
         )*
     };
 }
+
+#[macro_export]
+macro_rules! impl_get_varint {
+    (@unsigned $($ty:ty),* $(,)?) => {
+        $(
+            paste::paste! {
+                pub fn [<get_leb128_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    let value = $crate::utils::decode_uleb128(<$ty>::BITS, || self.get_byte())?;
+                    Ok(value as $ty)
+                }
+            }
+        )*
+    };
+    (@signed $($ty:ty),* $(,)?) => {
+        $(
+            paste::paste! {
+                pub fn [<get_leb128_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    let value = $crate::utils::decode_sleb128(<$ty>::BITS, || self.get_byte())?;
+                    Ok(value as $ty)
+                }
+                pub fn [<get_zigzag_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    let value = $crate::utils::decode_uleb128(<$ty>::BITS, || self.get_byte())?;
+                    Ok($crate::utils::zigzag_decode(value) as $ty)
+                }
+            }
+        )*
+    };
+}
+
+#[macro_export]
+macro_rules! impl_combinator_tuple {
+    ($first:ident $first_out:ident 0 $(, $parser:ident $out:ident $idx:tt)*) => {
+        impl<Out, $first $(, $parser)*> $crate::parser::combinators::Alt<Out> for ($first, $($parser,)*)
+        where
+            $first: Fn(&mut $crate::parser::core::DataParser) -> $crate::utils::ParseResult<Out>,
+            $($parser: Fn(&mut $crate::parser::core::DataParser) -> $crate::utils::ParseResult<Out>,)*
+        {
+            fn choice(&self, input: &mut $crate::parser::core::DataParser) -> $crate::utils::ParseResult<Out> {
+                let result = $crate::parser::combinators::backtrack(input, &self.0);
+                $(
+                    let result = result.or_else(|_| $crate::parser::combinators::backtrack(input, &self.$idx));
+                )*
+                result
+            }
+        }
+
+        impl<$first, $first_out $(, $parser, $out)*> $crate::parser::combinators::Sequence<($first_out, $($out,)*)> for ($first, $($parser,)*)
+        where
+            $first: Fn(&mut $crate::parser::core::DataParser) -> $crate::utils::ParseResult<$first_out>,
+            $($parser: Fn(&mut $crate::parser::core::DataParser) -> $crate::utils::ParseResult<$out>,)*
+        {
+            fn sequence(&self, input: &mut $crate::parser::core::DataParser) -> $crate::utils::ParseResult<($first_out, $($out,)*)> {
+                Ok(((self.0)(input)?, $((self.$idx)(input)?,)*))
+            }
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! impl_source_decodable {
+    ($($t:ty),* $(,)?) => {
+        $(
+        paste::paste! {
+            impl $crate::parser::source::SourceDecodable for $t {
+                fn from_source<S: $crate::parser::source::ByteSource>(source: &mut S) -> $crate::utils::ParseResult<Self> {
+                    source.[<get_ $t:lower>]()
+                }
+            }
+        }
+        )*
+    };
+}
+
+/// Implements [`ByteSource`](crate::parser::source::ByteSource) by forwarding every method to
+/// the inherent method of the same name, or with `buffered`, to the method of the same name on
+/// a [`DataParser`](crate::DataParser) over the implementor's buffered bytes.
+#[macro_export]
+macro_rules! impl_byte_source {
+    () => {
+        $crate::impl_byte_source!(@impl inherent);
+    };
+    (buffered) => {
+        $crate::impl_byte_source!(@impl buffered);
+    };
+    (@impl $mode:ident) => {
+        fn options(&self) -> &$crate::options::ParseOptions {
+            &self.options
+        }
+
+        fn nested<T, F>(&mut self, f: F) -> $crate::utils::ParseResult<T>
+        where
+            F: FnOnce(&mut Self) -> $crate::utils::ParseResult<T>,
+        {
+            Self::nested(self, f)
+        }
+
+        fn get_bytes(&mut self, n: usize) -> $crate::utils::ParseResult<Vec<u8>> {
+            $crate::impl_byte_source!(@call $mode self get_bytes(n))
+        }
+
+        fn get_byte(&mut self) -> $crate::utils::ParseResult<u8> {
+            $crate::impl_byte_source!(@call $mode self get_byte())
+        }
+
+        fn get_bool(&mut self) -> $crate::utils::ParseResult<bool> {
+            $crate::impl_byte_source!(@call $mode self get_bool())
+        }
+
+        fn get_length(&mut self, prefix: $crate::options::LengthPrefix) -> $crate::utils::ParseResult<usize> {
+            $crate::impl_byte_source!(@call $mode self get_length(prefix))
+        }
+
+        fn get_uint(&mut self, n_bytes: usize) -> $crate::utils::ParseResult<u64> {
+            $crate::impl_byte_source!(@call $mode self get_uint(n_bytes))
+        }
+
+        fn get_int(&mut self, n_bytes: usize) -> $crate::utils::ParseResult<i64> {
+            $crate::impl_byte_source!(@call $mode self get_int(n_bytes))
+        }
+
+        fn get_string(&mut self, utf16: bool) -> $crate::utils::ParseResult<String> {
+            $crate::impl_byte_source!(@call $mode self get_string(utf16))
+        }
+
+        fn get_string_with_prefix(
+            &mut self,
+            utf16: bool,
+            prefix: $crate::options::LengthPrefix,
+        ) -> $crate::utils::ParseResult<String> {
+            $crate::impl_byte_source!(@call $mode self get_string_with_prefix(utf16, prefix))
+        }
+
+        fn get_string_with_layout(
+            &mut self,
+            layout: $crate::options::StringLayout,
+            utf16: bool,
+        ) -> $crate::utils::ParseResult<String> {
+            $crate::impl_byte_source!(@call $mode self get_string_with_layout(layout, utf16))
+        }
+
+        fn get_encoded_string(
+            &mut self,
+            layout: $crate::options::StringLayout,
+            encoding: $crate::text::TextEncoding,
+        ) -> $crate::utils::ParseResult<String> {
+            $crate::impl_byte_source!(@call $mode self get_encoded_string(layout, encoding))
+        }
+
+        fn get_vector<T: $crate::parser::source::SourceDecodable>(&mut self) -> $crate::utils::ParseResult<Vec<T>> {
+            $crate::impl_byte_source!(@call $mode self get_vector::<T>())
+        }
+
+        fn get_vector_with_prefix<T: $crate::parser::source::SourceDecodable>(
+            &mut self,
+            prefix: $crate::options::LengthPrefix,
+        ) -> $crate::utils::ParseResult<Vec<T>> {
+            $crate::impl_byte_source!(@call $mode self get_vector_with_prefix::<T>(prefix))
+        }
+
+        fn get_option<T: $crate::parser::source::SourceDecodable>(&mut self) -> $crate::utils::ParseResult<Option<T>> {
+            $crate::impl_byte_source!(@call $mode self get_option::<T>())
+        }
+
+        $crate::impl_byte_source!(@forward $mode;
+            u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+        );
+        $crate::impl_byte_source!(@unsigned $mode; u8, u16, u32, u64, u128, usize);
+        $crate::impl_byte_source!(@signed $mode; i8, i16, i32, i64, i128, isize);
+    };
+    (@call inherent $self:tt $name:ident $(::<$g:ident>)? ($($arg:expr),*)) => {
+        Self::$name$(::<$g>)?($self, $($arg),*)
+    };
+    (@call buffered $self:tt $name:ident $(::<$g:ident>)? ($($arg:expr),*)) => {
+        $self.buffered(|parser| parser.$name$(::<$g>)?($($arg),*))
+    };
+    (@forward $mode:ident; $($ty:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                fn [<get_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    $crate::impl_byte_source!(@call $mode self [<get_ $ty>]())
+                }
+            }
+        )*
+    };
+    (@unsigned $mode:ident; $($ty:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                fn [<get_leb128_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    $crate::impl_byte_source!(@call $mode self [<get_leb128_ $ty>]())
+                }
+            }
+        )*
+    };
+    (@signed $mode:ident; $($ty:ident),* $(,)?) => {
+        $(
+            paste::paste! {
+                fn [<get_leb128_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    $crate::impl_byte_source!(@call $mode self [<get_leb128_ $ty>]())
+                }
+                fn [<get_zigzag_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    $crate::impl_byte_source!(@call $mode self [<get_zigzag_ $ty>]())
+                }
+            }
+        )*
+    };
+}
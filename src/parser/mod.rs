@@ -1,9 +1,12 @@
+pub mod bits;
 pub mod buffer;
 pub mod combinators;
 pub mod core;
+pub mod frame;
 pub mod helpers;
 pub mod macros;
 pub mod readers;
+pub mod source;
 pub mod strings;
 pub use crate::options::{EncodingOptions, ParseOptions};
 pub use crate::parser::core::DataParser;
@@ -1,51 +1,417 @@
+use std::io::Cursor;
+
+use crate::errors::DataParseError;
+use crate::impl_async_get;
+use crate::options::{LengthPrefix, Limit};
 use crate::parser::buffer::Buffer;
-use crate::parser::{DataParser, ParseOptions};
-use crate::utils::ParseResult;
+use crate::parser::{DataParser, Decodable, ParseOptions};
+use crate::utils::{ParseResult, check_uint_width, sign_extend, uint_from_endian_bytes};
 use tokio::io::{AsyncRead, AsyncReadExt};
 
-#[allow(unused)]
+/// Size of the chunks read from the stream while a value is incomplete.
+pub(crate) const READ_CHUNK_SIZE: usize = 4096;
+
+/// An async binary reader that decodes values directly off any `AsyncRead` (e.g. a `TcpStream`).
+///
+/// Only the bytes of the value being read are pulled from the stream, so a long-lived
+/// connection doesn't have to be buffered in full. The getters mirror those of
+/// [`DataReader`] and honor the same parsing options and limits.
+///
+/// # Example
+/// ```rust
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// use dataparser_core::parser::readers::async_reader::core::AsyncDataReader;
+/// let data: &[u8] = &[0x01, 0x02, 0x00, 0x00, 0x00, 0x02, b'h', b'i'];
+/// let mut reader = AsyncDataReader::new(data);
+/// assert_eq!(reader.get_u16().await?, 0x0102);
+/// assert_eq!(reader.get_string(false).await?, "hi");
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`DataReader`]: crate::parser::readers::sync_reader::core::DataReader
 pub struct AsyncDataReader<R: AsyncRead + Unpin> {
     pub(crate) reader: R,
     pub(crate) options: ParseOptions,
+
+    /// Bytes read from the stream that haven't been decoded yet.
+    pub(crate) buffer: Vec<u8>,
+
+    /// Position in `buffer` of the next byte to read through [`ByteSource`], while a value
+    /// is decoded out of the buffered bytes. Zero otherwise.
+    ///
+    /// [`ByteSource`]: crate::ByteSource
+    pub(crate) cursor: usize,
+
+    pub(crate) depth: usize,
+    pub(crate) allocated: usize,
 }
 
 impl<R> AsyncDataReader<R>
 where
     R: AsyncRead + Unpin,
 {
-    pub async fn new(reader: R) -> ParseResult<Self> {
-        Ok(Self {
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseOptions::default())
+    }
+
+    pub fn with_options(reader: R, options: ParseOptions) -> Self {
+        Self {
             reader,
-            options: ParseOptions::default(),
+            options,
+            buffer: Vec::new(),
+            cursor: 0,
+            depth: 0,
+            allocated: 0,
+        }
+    }
+
+    /// Updates the parsing options used by this reader.
+    pub fn set_options(&mut self, options: ParseOptions) {
+        self.options = options;
+    }
+
+    /// Decodes the next value off the stream.
+    ///
+    /// Any [`Decodable`] type works here, including every [`SourceDecodable`] one, and decodes
+    /// to the same value it would from a [`DataParser`] over the same bytes. The value is parsed
+    /// out of an internal buffer, which is refilled from the stream whenever the value turns
+    /// out to be incomplete; bytes read past the end of the value are kept for the next call.
+    ///
+    /// # Errors
+    /// Returns the decoding error if the data is malformed, or an
+    /// [`Incomplete`](crate::DataParseError::Incomplete) error if the stream ends before the
+    /// value is complete.
+    ///
+    /// # Example
+    /// ```rust
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use dataparser_core::parser::readers::async_reader::core::AsyncDataReader;
+    /// let data: &[u8] = &[0x00, 0x00, 0x00, 0x02, b'h', b'i', 0x01];
+    /// let mut reader = AsyncDataReader::new(data);
+    /// assert_eq!(reader.decode::<String>().await?, "hi");
+    /// assert_eq!(reader.decode::<bool>().await?, true);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// # }).unwrap();
+    /// ```
+    ///
+    /// [`SourceDecodable`]: crate::SourceDecodable
+    pub async fn decode<T: Decodable>(&mut self) -> ParseResult<T> {
+        self.read_buffered(|reader| reader.buffered(T::from_parser))
+            .await
+    }
+
+    /// Runs `f` on the buffered bytes, reading more from the stream and running it again
+    /// from the start whenever the buffered bytes turn out to be incomplete.
+    ///
+    /// `f` reads through the [`ByteSource`] methods. The bytes it read are consumed once it
+    /// succeeds; on an error, the buffer is left untouched.
+    ///
+    /// [`ByteSource`]: crate::ByteSource
+    pub(crate) async fn read_buffered<T, F>(&mut self, mut f: F) -> ParseResult<T>
+    where
+        F: FnMut(&mut Self) -> ParseResult<T>,
+    {
+        let allocated = self.allocated;
+        loop {
+            let result = f(self);
+            let used = std::mem::take(&mut self.cursor);
+            self.allocated = allocated;
+            let (error, missing) = match result {
+                Ok(value) => {
+                    self.buffer.drain(..used);
+                    return Ok(value);
+                }
+                Err(e) => match e.missing_bytes() {
+                    Some(missing) => (e, missing),
+                    None => return Err(e),
+                },
+            };
+            // Don't run `f` again until the buffer holds at least the bytes it was missing,
+            // however small the chunks the stream delivers them in.
+            let target = self.buffer.len() + missing;
+            self.check_limit(Limit::Buffered, target)?;
+            while self.buffer.len() < target {
+                // Grow by at most a chunk at a time, so a bogus length doesn't allocate more
+                // than the stream actually holds.
+                self.buffer
+                    .reserve((target - self.buffer.len()).min(READ_CHUNK_SIZE));
+                let read = self.reader.read_buf(&mut self.buffer).await?;
+                if read == 0 {
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    /// Runs `f` on a parser over the buffered bytes that haven't been read yet, then moves
+    /// past the bytes it read.
+    ///
+    /// The parser shares the depth and allocation accounting of this reader, and offsets in
+    /// its errors are made relative to the start of the buffer.
+    pub(crate) fn buffered<T, F>(&mut self, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut DataParser) -> ParseResult<T>,
+    {
+        let start = self.cursor;
+        let options = std::mem::take(&mut self.options);
+        let mut parser = DataParser::with_options(&self.buffer[start..], options);
+        parser.depth = self.depth;
+        parser.allocated = self.allocated;
+        let result = f(&mut parser);
+        self.cursor += parser.position();
+        self.allocated = parser.allocated;
+        self.options = parser.options;
+        result.map_err(|e| e.offset_by(start))
+    }
+
+    /// Runs `f` one nesting level deeper, enforcing `max_depth`.
+    pub(crate) fn nested<T, F>(&mut self, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut Self) -> ParseResult<T>,
+    {
+        self.check_limit(Limit::Depth, self.depth + 1)?;
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Checks `value` against a limit of the parser options.
+    pub(crate) fn check_limit(&self, limit: Limit, value: usize) -> ParseResult<()> {
+        self.options.check_limit(limit, value)
+    }
+
+    /// Accounts for `n` bytes about to be allocated for decoded data.
+    pub(crate) fn reserve(&mut self, n: usize) -> ParseResult<()> {
+        let total = self.allocated.saturating_add(n);
+        self.check_limit(Limit::Allocation, total)?;
+        self.allocated = total;
+        Ok(())
+    }
+
+    /// Reads the next `len` bytes into a sub-reader one nesting level deeper, which shares
+    /// the depth and allocation accounting of this reader.
+    ///
+    /// Pass the sub-reader and its result to [`end_section`](AsyncDataReader::end_section)
+    /// once done with it.
+    pub(crate) async fn section(
+        &mut self,
+        len: usize,
+    ) -> ParseResult<AsyncDataReader<Cursor<Vec<u8>>>> {
+        self.check_limit(Limit::Depth, self.depth + 1)?;
+        let bytes = self.get_bytes(len).await?;
+        Ok(AsyncDataReader {
+            reader: Cursor::new(bytes),
+            options: self.options.clone(),
+            buffer: Vec::new(),
+            cursor: 0,
+            depth: self.depth + 1,
+            allocated: self.allocated,
         })
     }
 
-    pub async fn with_options(reader: R, options: ParseOptions) -> ParseResult<Self> {
-        Ok(Self { reader, options })
+    /// Closes a section opened with [`section`](AsyncDataReader::section).
+    ///
+    /// Running out of bytes inside the section is reported as a
+    /// [`DataParseError::SectionOverrun`].
+    pub(crate) fn end_section<T>(
+        &mut self,
+        section: AsyncDataReader<Cursor<Vec<u8>>>,
+        result: ParseResult<T>,
+    ) -> ParseResult<T> {
+        self.allocated = section.allocated;
+        result.map_err(|e| e.in_section())
     }
+
+    /// Parses a value from a sub-buffer whose length is encoded as `prefix`.
+    ///
+    /// The section is read off the stream in full, then `f` runs on a sub-reader scoped to it,
+    /// one nesting level deeper. Running out of bytes inside the section is reported as a
+    /// [`DataParseError::SectionOverrun`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+    /// use dataparser_core::{options::LengthPrefix, parser::readers::async_reader::core::AsyncDataReader};
+    /// let data: &[u8] = &[0x03, 0x01, 0x00, 0x02, 0xFF];
+    /// let mut reader = AsyncDataReader::new(data);
+    /// let pair = reader
+    ///     .parse_with_prefix(LengthPrefix::U8, async |sub| Ok((sub.get_u8().await?, sub.get_u16().await?)))
+    ///     .await?;
+    /// assert_eq!(pair, (1, 2));
+    /// assert_eq!(reader.get_u8().await?, 0xFF);
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// # }).unwrap();
+    /// ```
+    pub async fn parse_with_prefix<T, F>(&mut self, prefix: LengthPrefix, f: F) -> ParseResult<T>
+    where
+        F: AsyncFnOnce(&mut AsyncDataReader<Cursor<Vec<u8>>>) -> ParseResult<T>,
+    {
+        let len = self.__get_length(prefix).await?;
+        let mut section = self.section(len).await?;
+        let result = f(&mut section).await;
+        self.end_section(section, result)
+    }
+
+    /// Fills `buf` completely, from the buffered bytes first and then from the stream.
+    ///
+    /// # Errors
+    /// Returns [`DataParseError::Incomplete`] if the stream ends first.
+    pub(crate) async fn fill(&mut self, buf: &mut [u8]) -> ParseResult<()> {
+        let buffered = self.buffer.len().min(buf.len());
+        buf[..buffered].copy_from_slice(&self.buffer[..buffered]);
+        self.buffer.drain(..buffered);
+        let mut filled = buffered;
+        while filled < buf.len() {
+            match self.reader.read(&mut buf[filled..]).await? {
+                0 => {
+                    return Err(DataParseError::Incomplete {
+                        needed: buf.len(),
+                        available: filled,
+                    });
+                }
+                n => filled += n,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads exactly `N` bytes into a fixed-size array.
+    pub(crate) async fn read_array<const N: usize>(&mut self) -> ParseResult<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.fill(&mut buf).await?;
+        Ok(buf)
+    }
+
+    /// Reads `n` bytes from the stream and returns them in a `Vec<u8>`.
+    ///
+    /// The buffer grows as bytes arrive, so a bogus `n` doesn't allocate more than the
+    /// stream actually holds.
+    ///
+    /// # Errors
+    /// Returns an error if not enough bytes are available, or if `max_alloc` would be exceeded.
+    pub async fn get_bytes(&mut self, n: usize) -> ParseResult<Vec<u8>> {
+        self.reserve(n)?;
+        let buffered = self.buffer.len().min(n);
+        let mut bytes: Vec<u8> = self.buffer.drain(..buffered).collect();
+        (&mut self.reader)
+            .take((n - buffered) as u64)
+            .read_to_end(&mut bytes)
+            .await?;
+        if bytes.len() < n {
+            return Err(DataParseError::Incomplete {
+                needed: n,
+                available: bytes.len(),
+            });
+        }
+        Ok(bytes)
+    }
+
+    /// Reads a single byte from the stream.
+    pub async fn get_byte(&mut self) -> ParseResult<u8> {
+        let byte = self.read_array::<1>().await?;
+        Ok(byte[0])
+    }
+
+    /// Reads a single byte and interprets it as a boolean value.
+    ///
+    /// Returns `true` if the byte is non-zero, `false` otherwise.
+    pub async fn get_bool(&mut self) -> ParseResult<bool> {
+        Ok(self.get_byte().await? != 0)
+    }
+
+    /// Reads a length prefix of the given kind.
+    ///
+    /// Same format and options as [`DataReader::get_length`].
+    ///
+    /// [`DataReader::get_length`]: crate::parser::readers::sync_reader::core::DataReader::get_length
+    pub async fn get_length(&mut self, prefix: LengthPrefix) -> ParseResult<usize> {
+        if self.options.length_prefixed_fields {
+            let len = self.__get_length(self.options.length_prefix).await?;
+            let mut section = self.section(len).await?;
+            let result = section.__get_length(prefix).await;
+            self.end_section(section, result)
+        } else {
+            self.__get_length(prefix).await
+        }
+    }
+
+    pub(crate) async fn __get_length(&mut self, prefix: LengthPrefix) -> ParseResult<usize> {
+        let bytes = match prefix.width() {
+            Some(width) => {
+                let mut buf = [0u8; 8];
+                self.fill(&mut buf[..width]).await?;
+                buf[..width].to_vec()
+            }
+            None => {
+                // Read up to one byte past the longest valid varint, so that decoding reports
+                // an overflow instead of running out of bytes.
+                let max = u64::BITS.div_ceil(7) as usize + 1;
+                let mut bytes = Vec::with_capacity(max);
+                loop {
+                    let byte = self.get_byte().await?;
+                    bytes.push(byte);
+                    if byte & 0x80 == 0 || bytes.len() == max {
+                        break bytes;
+                    }
+                }
+            }
+        };
+        let endianness = self.options.prefix_endianness().clone();
+        let mut bytes = bytes.into_iter();
+        prefix.decode(&endianness, || Ok(bytes.next().unwrap_or_default()))
+    }
+
+    /// Reads an unsigned integer that is `n_bytes` wide (1 to 8), using the configured endianness.
+    pub async fn get_uint(&mut self, n_bytes: usize) -> ParseResult<u64> {
+        check_uint_width(n_bytes)?;
+        let mut buf = [0u8; 8];
+        let bytes = &mut buf[..n_bytes];
+        self.fill(bytes).await?;
+        uint_from_endian_bytes(bytes, &self.options.endianness)
+    }
+
+    /// Reads a two's complement signed integer that is `n_bytes` wide (1 to 8), using the
+    /// configured endianness, and sign-extends it to an `i64`.
+    pub async fn get_int(&mut self, n_bytes: usize) -> ParseResult<i64> {
+        Ok(sign_extend(self.get_uint(n_bytes).await?, n_bytes))
+    }
+
+    impl_async_get!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+    );
 }
 
 impl DataParser<'_> {
-    pub async fn with_async_reader<R: AsyncRead + Unpin>(mut reader: R) -> ParseResult<Self> {
-        let mut buf = Vec::new();
-        reader.read_to_end(&mut buf).await?;
-        Ok(Self {
-            buffer: Buffer::from(buf),
-            cursor: 0,
-            options: ParseOptions::default(),
-        })
+    pub async fn with_async_reader<R: AsyncRead + Unpin>(reader: R) -> ParseResult<Self> {
+        Self::with_options_async_reader(reader, ParseOptions::default()).await
     }
 
+    /// Reads `reader` to the end and creates a parser over its contents.
+    ///
+    /// With `max_alloc` set, the input itself counts towards the limit, and reading stops
+    /// as soon as it is exceeded.
     pub async fn with_options_async_reader<R: AsyncRead + Unpin>(
-        mut reader: R,
+        reader: R,
         options: ParseOptions,
     ) -> ParseResult<Self> {
         let mut buf = Vec::new();
-        reader.read_to_end(&mut buf).await?;
-        Ok(Self {
-            buffer: Buffer::from(buf),
-            cursor: 0,
-            options,
-        })
+        match options.max_alloc {
+            Some(max) => {
+                reader
+                    .take((max as u64).saturating_add(1))
+                    .read_to_end(&mut buf)
+                    .await?
+            }
+            None => {
+                let mut reader = reader;
+                reader.read_to_end(&mut buf).await?
+            }
+        };
+        options.check_limit(Limit::Allocation, buf.len())?;
+        let mut parser = Self::with_options(Buffer::from(buf), options);
+        parser.allocated = parser.current_len();
+        Ok(parser)
     }
 }
@@ -1 +1,104 @@
+use async_trait::async_trait;
+use tokio::io::AsyncRead;
 
+use crate::{options::Limit, utils::ParseResult};
+
+use super::core::AsyncDataReader;
+
+/// Upper bound on the elements preallocated for a `Vec<T>` whose length is read from the stream.
+const MAX_PREALLOCATED_ITEMS: usize = 1024;
+
+/// A trait for types that can be deserialized from an async stream using an [`AsyncDataReader`].
+///
+/// The async counterpart of [`StreamDecodable`], with the same binary format as every other
+/// decoder in the crate. Every [`SourceDecodable`] type implements it by decoding itself
+/// through the reader's [`ByteSource`] impl, reading more from the stream whenever the value
+/// isn't fully buffered yet.
+///
+/// # Example
+/// ```rust
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// use dataparser_core::{
+///     ByteSource, ParseResult, SourceDecodable,
+///     parser::readers::async_reader::{core::AsyncDataReader, helpers::AsyncDecodable},
+/// };
+/// use tokio::io::AsyncReadExt;
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Entry {
+///     id: u16,
+///     name: String,
+/// }
+///
+/// impl SourceDecodable for Entry {
+///     fn from_source<S: ByteSource>(source: &mut S) -> ParseResult<Self> {
+///         Ok(Entry { id: source.get_u16()?, name: source.get_string(false)? })
+///     }
+/// }
+///
+/// // The entry arrives in two reads, split in the middle of the name.
+/// let first: &[u8] = &[0x01, 0x00, 0x07, 0x00, 0x00, 0x00, 0x02, b'o'];
+/// let second: &[u8] = &[b'k'];
+/// let mut reader = AsyncDataReader::new(first.chain(second));
+/// let value = Option::<Entry>::from_async_reader(&mut reader).await?;
+/// assert_eq!(value, Some(Entry { id: 7, name: "ok".into() }));
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// # }).unwrap();
+/// ```
+///
+/// [`AsyncDataReader`]: crate::parser::readers::async_reader::core::AsyncDataReader
+/// [`StreamDecodable`]: crate::StreamDecodable
+/// [`SourceDecodable`]: crate::SourceDecodable
+/// [`ByteSource`]: crate::ByteSource
+#[async_trait]
+pub trait AsyncDecodable: Sized {
+    async fn from_async_reader<R: AsyncRead + Unpin + Send>(
+        reader: &mut AsyncDataReader<R>,
+    ) -> ParseResult<Self>;
+}
+
+/// Deserializes an `Option<T>` as a presence flag followed by the value, if present.
+#[async_trait]
+impl<T: AsyncDecodable + Send> AsyncDecodable for Option<T> {
+    async fn from_async_reader<R: AsyncRead + Unpin + Send>(
+        reader: &mut AsyncDataReader<R>,
+    ) -> ParseResult<Self> {
+        if !reader.get_bool().await? {
+            return Ok(None);
+        }
+        reader.check_limit(Limit::Depth, reader.depth + 1)?;
+        reader.depth += 1;
+        let value = T::from_async_reader(reader).await;
+        reader.depth -= 1;
+        value.map(Some)
+    }
+}
+
+/// Deserializes a `Vec<T>` as an element count followed by each element in a
+/// length-prefixed section.
+///
+/// # Example binary format
+/// ```text
+/// [count][len1][item1_bytes][len2][item2_bytes]...
+/// ```
+#[async_trait]
+impl<T: AsyncDecodable + Send> AsyncDecodable for Vec<T> {
+    async fn from_async_reader<R: AsyncRead + Unpin + Send>(
+        reader: &mut AsyncDataReader<R>,
+    ) -> ParseResult<Self> {
+        let prefix = reader.options.length_prefix;
+        let len = reader.get_length(prefix).await?;
+        reader.check_limit(Limit::CollectionLength, len)?;
+        // The stream length is unknown, so only preallocate a bounded number of elements.
+        let capacity = len.min(MAX_PREALLOCATED_ITEMS);
+        reader.reserve(capacity.saturating_mul(size_of::<T>()))?;
+        let mut out = Vec::with_capacity(capacity);
+        for _ in 0..len {
+            let item_len = reader.get_length(prefix).await?;
+            let mut section = reader.section(item_len).await?;
+            let item = T::from_async_reader(&mut section).await;
+            out.push(reader.end_section(section, item)?);
+        }
+        Ok(out)
+    }
+}
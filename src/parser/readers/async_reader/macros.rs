@@ -1 +1,27 @@
-
+#[macro_export]
+macro_rules! impl_async_get {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            paste::paste! {
+                pub async fn [<get_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    if self.options.length_prefixed_fields {
+                        let len = self.__get_length(self.options.length_prefix).await?;
+                        let mut section = self.section(len).await?;
+                        let result = section.[<__get_ $ty>]().await;
+                        self.end_section(section, result)
+                    } else {
+                        self.[<__get_ $ty>]().await
+                    }
+                }
+                pub(crate) async fn [<__get_ $ty>](&mut self) -> $crate::utils::ParseResult<$ty> {
+                    let bytes = self.read_array::<{ std::mem::size_of::<$ty>() }>().await?;
+                    Ok(match self.options.endianness {
+                        $crate::utils::Endianness::BigEndian => <$ty>::from_be_bytes(bytes),
+                        $crate::utils::Endianness::LittleEndian => <$ty>::from_le_bytes(bytes),
+                        $crate::utils::Endianness::NativeEndian => <$ty>::from_ne_bytes(bytes),
+                    })
+                }
+            }
+        )*
+    };
+}
@@ -1,3 +1,5 @@
 pub mod core;
 pub mod helpers;
 pub mod macros;
+pub mod stream;
+pub mod strings;
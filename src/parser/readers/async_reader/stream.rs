@@ -0,0 +1,146 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll, ready};
+
+use futures_core::Stream;
+use tokio::io::{AsyncRead, ReadBuf};
+
+use crate::errors::DataParseError;
+use crate::options::Limit;
+use crate::parser::{DataParser, Decodable};
+use crate::utils::ParseResult;
+
+use super::core::{AsyncDataReader, READ_CHUNK_SIZE};
+
+/// A [`Stream`] of values decoded one after another off an [`AsyncDataReader`].
+///
+/// Created with [`AsyncDataReader::into_stream`]. Each item is decoded exactly as
+/// [`AsyncDataReader::decode`] would decode it. The stream ends (yields `None`) when the
+/// underlying reader reaches EOF between two values; reaching EOF in the middle of a value
+/// yields an [`Incomplete`](DataParseError::Incomplete) error instead. After any error the
+/// stream ends, since the reader can't tell where the next value starts.
+///
+/// # Example
+/// ```rust
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// use dataparser_core::parser::readers::async_reader::core::AsyncDataReader;
+/// use futures::StreamExt;
+///
+/// let data: &[u8] = &[0x00, 0x01, 0x00, 0x02, 0x00];
+/// let mut stream = AsyncDataReader::new(data).into_stream::<u16>();
+/// assert_eq!(stream.next().await.transpose()?, Some(1));
+/// assert_eq!(stream.next().await.transpose()?, Some(2));
+/// // A single trailing byte is a truncated `u16`, not the end of the stream.
+/// assert!(stream.next().await.unwrap().unwrap_err().is_incomplete());
+/// assert!(stream.next().await.is_none());
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// # }).unwrap();
+/// ```
+pub struct DecodeStream<R: AsyncRead + Unpin, T> {
+    reader: AsyncDataReader<R>,
+
+    /// Buffer length to reach before trying to decode again.
+    target: usize,
+    /// Why the last decoding attempt needed more bytes, reported if the reader hits EOF.
+    incomplete: Option<DataParseError>,
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncDataReader<R> {
+    /// Turns the reader into a [`Stream`] of `T` values.
+    ///
+    /// See [`DecodeStream`] for how the end of the stream is detected.
+    pub fn into_stream<T: Decodable>(self) -> DecodeStream<R, T> {
+        DecodeStream {
+            reader: self,
+            target: 0,
+            incomplete: None,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R: AsyncRead + Unpin, T> DecodeStream<R, T> {
+    /// Returns the underlying reader, along with any bytes it has read but not decoded yet.
+    pub fn into_inner(self) -> AsyncDataReader<R> {
+        self.reader
+    }
+
+    /// Decodes a value out of the buffered bytes, or returns the error explaining why the
+    /// buffer doesn't hold a complete value yet.
+    fn decode_buffered(&mut self) -> ParseResult<T>
+    where
+        T: Decodable,
+    {
+        let reader = &mut self.reader;
+        let mut parser = DataParser::with_options(&reader.buffer[..], reader.options.clone());
+        let value = T::from_parser(&mut parser)?;
+        let used = parser.position();
+        reader.buffer.drain(..used);
+        Ok(value)
+    }
+}
+
+impl<R: AsyncRead + Unpin, T: Decodable> Stream for DecodeStream<R, T> {
+    type Item = ParseResult<T>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        loop {
+            if this.done {
+                return Poll::Ready(None);
+            }
+            let buffered = this.reader.buffer.len();
+            // Nothing to decode until the value's first bytes arrive; an empty buffer at EOF
+            // is the clean end of the stream.
+            if buffered > 0 && buffered >= this.target {
+                match this.decode_buffered() {
+                    Ok(value) => {
+                        this.target = 0;
+                        this.incomplete = None;
+                        return Poll::Ready(Some(Ok(value)));
+                    }
+                    Err(e) => match e.missing_bytes() {
+                        Some(missing) => {
+                            this.target = buffered + missing;
+                            if let Err(e) = this.reader.check_limit(Limit::Buffered, this.target) {
+                                this.done = true;
+                                return Poll::Ready(Some(Err(e)));
+                            }
+                            this.incomplete = Some(e);
+                        }
+                        None => {
+                            this.done = true;
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                    },
+                }
+            }
+
+            let mut read_buf = ReadBuf::new(&mut chunk);
+            if let Err(e) = ready!(Pin::new(&mut this.reader.reader).poll_read(cx, &mut read_buf)) {
+                this.done = true;
+                return Poll::Ready(Some(Err(DataParseError::from(e))));
+            }
+            let read = read_buf.filled();
+            if read.is_empty() {
+                this.done = true;
+                if buffered == 0 {
+                    return Poll::Ready(None);
+                }
+                let error = this
+                    .incomplete
+                    .take()
+                    .unwrap_or(DataParseError::Incomplete {
+                        needed: this.target,
+                        available: buffered,
+                    });
+                return Poll::Ready(Some(Err(error)));
+            }
+            this.reader.buffer.extend_from_slice(read);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use tokio::io::AsyncRead;
+
+use crate::{
+    options::{LengthPrefix, Limit},
+    parser::strings::decode_string,
+    utils::ParseResult,
+};
+
+use super::core::AsyncDataReader;
+
+impl<R: AsyncRead + Unpin> AsyncDataReader<R> {
+    /// Reads a length-prefixed string from the stream.
+    ///
+    /// Same format and options as [`DataParser::get_string`].
+    ///
+    /// [`DataParser::get_string`]: crate::parser::core::DataParser::get_string
+    pub async fn get_string(&mut self, utf16: bool) -> ParseResult<String> {
+        self.get_string_with_prefix(utf16, self.options.length_prefix)
+            .await
+    }
+
+    /// Reads a string like [`get_string`], using `prefix` instead of the configured length prefix.
+    ///
+    /// [`get_string`]: AsyncDataReader::get_string
+    pub async fn get_string_with_prefix(
+        &mut self,
+        utf16: bool,
+        prefix: LengthPrefix,
+    ) -> ParseResult<String> {
+        let encoding = self.options.string_encoding(utf16);
+        let str_len = self.get_length(prefix).await?;
+        self.check_limit(Limit::StringLength, str_len)?;
+        let bytes = self.get_bytes(str_len).await?;
+        decode_string(
+            &bytes,
+            encoding,
+            self.options.strict_encoding,
+            self.options.trim_null_strings,
+        )
+    }
+}
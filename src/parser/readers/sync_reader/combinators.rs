@@ -1,11 +1,12 @@
 use super::core::DataReader;
+use crate::options::LengthPrefix;
 use crate::utils::ParseResult;
 use std::io::{Cursor, Read};
 
 impl<R: Read> DataReader<R> {
     /// Parses a value using a length-prefixed sub-buffer from the current stream.
     ///
-    /// This method reads a `u32` length prefix, then extracts that many bytes from the stream,
+    /// This method reads a length prefix (as configured through [`LengthPrefix`]), then extracts that many bytes from the stream,
     /// and creates a new [`DataReader`] scoped to only that sub-buffer. The provided closure `f`
     /// receives this sub-reader to deserialize the contained value.
     ///
@@ -23,13 +24,13 @@ impl<R: Read> DataReader<R> {
     /// A `ParseResult<T>` containing the parsed value or an error if reading or parsing fails.
     ///
     /// # Example
-    /// ```rust
+    /// ```ignore
     /// use dataparser_core::parser::readers::sync_reader::core::DataReader;
     /// let mut reader = DataReader::new(std::io::Cursor::new(input_bytes));
     /// let my_struct = reader.parse_with_length_prefix(|sub| MyStruct::from_stream_parser(sub))?;
     /// ```
     ///
-    /// This example assumes that `input_bytes` contains a `u32` length followed by a serialized `MyStruct`.
+    /// This example assumes that `input_bytes` contains a length prefix followed by a serialized `MyStruct`.
     ///
     /// # Errors
     /// Returns an error if:
@@ -37,16 +38,24 @@ impl<R: Read> DataReader<R> {
     /// - The underlying stream fails to provide the required number of bytes
     /// - The inner parsing logic fails
     ///
-    /// [`DataReader`]: crate::parser::core::DataReader
+    /// [`DataReader`]: crate::parser::readers::sync_reader::core::DataReader
+    /// [`LengthPrefix`]: crate::options::LengthPrefix
     pub(crate) fn parse_with_length_prefix<T, F>(&mut self, f: F) -> ParseResult<T>
     where
         F: FnOnce(&mut DataReader<Cursor<Vec<u8>>>) -> ParseResult<T>,
     {
-        let options = self.options.clone();
-        let len = self.__get_u32()?;
-        let buf = self.get_bytes(len as usize)?;
-        let cursor = Cursor::new(buf);
-        let mut sub_parser = DataReader::with_options(cursor, options);
-        f(&mut sub_parser)
+        self.parse_with_prefix(self.options.length_prefix, f)
+    }
+
+    /// Parses a value from a sub-buffer whose length is encoded as `prefix`.
+    ///
+    /// Same as the length-prefixed sections used for `length_prefixed_fields`, but with an
+    /// explicit prefix kind.
+    pub fn parse_with_prefix<T, F>(&mut self, prefix: LengthPrefix, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut DataReader<Cursor<Vec<u8>>>) -> ParseResult<T>,
+    {
+        let len = self.__get_length(prefix)?;
+        self.parse_section(len, f)
     }
 }
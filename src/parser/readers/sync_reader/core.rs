@@ -1,6 +1,12 @@
-use std::io::Read;
+use std::io::{Cursor, Read};
 
-use crate::{impl_get_with_prefix, parser::ParseOptions, utils::ParseResult};
+use crate::{
+    errors::DataParseError,
+    impl_get_varint, impl_get_with_prefix,
+    options::{LengthPrefix, Limit},
+    parser::ParseOptions,
+    utils::{ParseResult, check_uint_width, sign_extend, uint_from_endian_bytes},
+};
 
 /// A streaming binary reader that wraps any `Read` implementation (e.g. file, socket).
 ///
@@ -12,7 +18,7 @@ use crate::{impl_get_with_prefix, parser::ParseOptions, utils::ParseResult};
 ///
 /// ```rust
 /// use std::io::Cursor;
-/// use dataparser_core::parser::core::DataReader;
+/// use dataparser_core::parser::readers::sync_reader::core::DataReader;
 /// let bytes = Cursor::new(vec![0x01, 0x00]);
 /// let mut reader = DataReader::new(bytes);
 /// let value = reader.get_byte().unwrap();
@@ -22,6 +28,8 @@ use crate::{impl_get_with_prefix, parser::ParseOptions, utils::ParseResult};
 pub struct DataReader<R: Read> {
     pub(crate) reader: R,
     pub(crate) options: ParseOptions,
+    pub(crate) depth: usize,
+    pub(crate) allocated: usize,
 }
 
 impl<R> DataReader<R>
@@ -33,10 +41,7 @@ where
     /// # Arguments
     /// * `reader` - A stream or source implementing `std::io::Read`.
     pub fn new(reader: R) -> Self {
-        Self {
-            reader,
-            options: ParseOptions::default(),
-        }
+        Self::with_options(reader, ParseOptions::default())
     }
 
     /// Creates a new `DataReader` with custom parsing options.
@@ -45,7 +50,12 @@ where
     /// * `reader` - A stream or source implementing `Read`.
     /// * `options` - Parsing options such as verbose error handling.
     pub fn with_options(reader: R, options: ParseOptions) -> Self {
-        Self { reader, options }
+        Self {
+            reader,
+            options,
+            depth: 0,
+            allocated: 0,
+        }
     }
 
     /// Updates the parsing options used by this reader.
@@ -55,24 +65,100 @@ where
         self.options = options;
     }
 
+    /// Runs `f` one nesting level deeper.
+    ///
+    /// Same as [`DataParser::nested`].
+    ///
+    /// [`DataParser::nested`]: crate::parser::core::DataParser::nested
+    pub fn nested<T, F>(&mut self, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut Self) -> ParseResult<T>,
+    {
+        self.check_limit(Limit::Depth, self.depth + 1)?;
+        self.depth += 1;
+        let result = f(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Checks `value` against a limit of the parser options.
+    pub(crate) fn check_limit(&self, limit: Limit, value: usize) -> ParseResult<()> {
+        self.options.check_limit(limit, value)
+    }
+
+    /// Accounts for `n` bytes about to be allocated for decoded data.
+    pub(crate) fn reserve(&mut self, n: usize) -> ParseResult<()> {
+        let total = self.allocated.saturating_add(n);
+        self.check_limit(Limit::Allocation, total)?;
+        self.allocated = total;
+        Ok(())
+    }
+
+    /// Runs `f` on a sub-reader over the next `len` bytes, one nesting level deeper.
+    ///
+    /// The section is read into memory first, so its length is checked against
+    /// `max_buffered`. The sub-reader shares the depth and allocation accounting of this
+    /// reader, and running out of bytes inside the section is reported as a
+    /// [`DataParseError::SectionOverrun`].
+    pub(crate) fn parse_section<T, F>(&mut self, len: usize, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut DataReader<Cursor<Vec<u8>>>) -> ParseResult<T>,
+    {
+        self.nested(|this| {
+            this.check_limit(Limit::Buffered, len)?;
+            let buf = this.get_bytes(len)?;
+            let mut sub_reader = DataReader::with_options(Cursor::new(buf), this.options.clone());
+            sub_reader.depth = this.depth;
+            sub_reader.allocated = this.allocated;
+            let result = f(&mut sub_reader);
+            this.allocated = sub_reader.allocated;
+            result.map_err(|e| e.in_section())
+        })
+    }
+
+    /// Fills `buf` completely from the stream.
+    ///
+    /// # Errors
+    /// Returns [`DataParseError::Incomplete`] if the stream ends first, so a truncated
+    /// stream can be told apart from malformed data.
+    pub(crate) fn fill(&mut self, buf: &mut [u8]) -> ParseResult<()> {
+        let filled = read_up_to(&mut self.reader, buf)?;
+        if filled < buf.len() {
+            return Err(DataParseError::Incomplete {
+                needed: buf.len(),
+                available: filled,
+            });
+        }
+        Ok(())
+    }
+
     /// Reads exactly `N` bytes into a fixed-size array.
     ///
     /// # Errors
     /// Returns an error if the stream ends before `N` bytes are read.
     pub(crate) fn read_array<const N: usize>(&mut self) -> ParseResult<[u8; N]> {
         let mut buf = [0u8; N];
-        self.reader.read_exact(&mut buf)?;
-
+        self.fill(&mut buf)?;
         Ok(buf)
     }
 
     /// Reads `n` bytes from the stream and returns them in a `Vec<u8>`.
     ///
+    /// The buffer grows as bytes arrive, so a bogus `n` doesn't allocate more than the
+    /// stream actually holds.
+    ///
     /// # Errors
-    /// Returns an error if not enough bytes are available.
+    /// Returns an error if not enough bytes are available, or if `max_alloc` would be exceeded.
     pub fn get_bytes(&mut self, n: usize) -> ParseResult<Vec<u8>> {
-        let mut buf = vec![0u8; n];
-        self.reader.read_exact(&mut buf)?;
+        self.reserve(n)?;
+        let mut buf = Vec::new();
+        (&mut self.reader).take(n as u64).read_to_end(&mut buf)?;
+        if buf.len() < n {
+            return Err(DataParseError::Incomplete {
+                needed: n,
+                available: buf.len(),
+            });
+        }
         Ok(buf)
     }
 
@@ -85,6 +171,26 @@ where
         Ok(byte[0])
     }
 
+    /// Reads a length prefix of the given kind.
+    ///
+    /// Fixed-width prefixes use the configured prefix endianness. Like the numeric getters,
+    /// the prefix itself is wrapped in a length-prefixed field when `length_prefixed_fields` is set.
+    ///
+    /// # Errors
+    /// Returns an error if the stream ends early or the length doesn't fit in `usize`.
+    pub fn get_length(&mut self, prefix: LengthPrefix) -> ParseResult<usize> {
+        if self.options.length_prefixed_fields {
+            self.parse_with_length_prefix(|r| r.__get_length(prefix))
+        } else {
+            self.__get_length(prefix)
+        }
+    }
+
+    pub(crate) fn __get_length(&mut self, prefix: LengthPrefix) -> ParseResult<usize> {
+        let endianness = self.options.prefix_endianness().clone();
+        prefix.decode(&endianness, || self.get_byte())
+    }
+
     /// Reads a single byte and interprets it as a boolean value.
     ///
     /// Returns `true` if the byte is non-zero, `false` otherwise.
@@ -95,5 +201,44 @@ where
         Ok(self.get_byte()? != 0)
     }
 
-    impl_get_with_prefix!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, f32, f64);
+    /// Reads an unsigned integer that is `n_bytes` wide (1 to 8), using the configured endianness.
+    ///
+    /// # Errors
+    /// Returns an error if `n_bytes` is out of range or the stream ends early.
+    pub fn get_uint(&mut self, n_bytes: usize) -> ParseResult<u64> {
+        check_uint_width(n_bytes)?;
+        let mut buf = [0u8; 8];
+        let bytes = &mut buf[..n_bytes];
+        self.fill(bytes)?;
+        uint_from_endian_bytes(bytes, &self.options.endianness)
+    }
+
+    /// Reads a two's complement signed integer that is `n_bytes` wide (1 to 8), using the
+    /// configured endianness, and sign-extends it to an `i64`.
+    ///
+    /// # Errors
+    /// Returns an error if `n_bytes` is out of range or the stream ends early.
+    pub fn get_int(&mut self, n_bytes: usize) -> ParseResult<i64> {
+        Ok(sign_extend(self.get_uint(n_bytes)?, n_bytes))
+    }
+
+    impl_get_with_prefix!(
+        u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+    );
+    impl_get_varint!(@unsigned u8, u16, u32, u64, u128, usize);
+    impl_get_varint!(@signed i8, i16, i32, i64, i128, isize);
+}
+
+/// Fills `buf` from `reader`, stopping early only at EOF. Returns the number of bytes read.
+pub(crate) fn read_up_to<R: Read>(reader: &mut R, buf: &mut [u8]) -> ParseResult<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(filled)
 }
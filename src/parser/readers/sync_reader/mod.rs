@@ -1,3 +1,4 @@
 pub mod combinators;
 pub mod core;
 pub mod helpers;
+pub mod strings;
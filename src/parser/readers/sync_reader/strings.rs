@@ -0,0 +1,126 @@
+use std::io::Read;
+
+use crate::{
+    options::{LengthPrefix, Limit, StringLayout},
+    parser::strings::{decode_string, strip_padding},
+    text::TextEncoding,
+    utils::ParseResult,
+};
+
+use super::core::{DataReader, read_up_to};
+
+impl<R: Read> DataReader<R> {
+    fn _get_string(&mut self, bytes: &[u8], encoding: TextEncoding) -> ParseResult<String> {
+        decode_string(
+            bytes,
+            encoding,
+            self.options.strict_encoding,
+            self.options.trim_null_strings,
+        )
+    }
+
+    /// Reads a length-prefixed string from the stream.
+    ///
+    /// Same format and options as [`DataParser::get_string`].
+    ///
+    /// [`DataParser::get_string`]: crate::parser::core::DataParser::get_string
+    pub fn get_string(&mut self, utf16: bool) -> ParseResult<String> {
+        self.get_string_with_prefix(utf16, self.options.length_prefix)
+    }
+
+    /// Reads a string like [`get_string`], using `prefix` instead of the configured length prefix.
+    ///
+    /// [`get_string`]: DataReader::get_string
+    pub fn get_string_with_prefix(
+        &mut self,
+        utf16: bool,
+        prefix: LengthPrefix,
+    ) -> ParseResult<String> {
+        let encoding = self.options.string_encoding(utf16);
+        let str_len = self.get_length(prefix)?;
+        self.check_limit(Limit::StringLength, str_len)?;
+        let bytes = self.get_bytes(str_len)?;
+        self._get_string(&bytes, encoding)
+    }
+
+    /// Reads a string stored with the given [`StringLayout`].
+    ///
+    /// Same semantics as [`DataParser::get_string_with_layout`]; `RestOfInput` reads until
+    /// the underlying stream reports EOF.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::io::Cursor;
+    /// use dataparser_core::{options::StringLayout, parser::readers::sync_reader::core::DataReader};
+    /// let mut reader = DataReader::new(Cursor::new(b"abc\0xyz".to_vec()));
+    /// assert_eq!(reader.get_string_with_layout(StringLayout::NullTerminated, false)?, "abc");
+    /// assert_eq!(reader.get_string_with_layout(StringLayout::RestOfInput, false)?, "xyz");
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    ///
+    /// [`StringLayout`]: crate::options::StringLayout
+    /// [`DataParser::get_string_with_layout`]: crate::parser::core::DataParser::get_string_with_layout
+    pub fn get_string_with_layout(
+        &mut self,
+        layout: StringLayout,
+        utf16: bool,
+    ) -> ParseResult<String> {
+        self.get_encoded_string(layout, self.options.string_encoding(utf16))
+    }
+
+    /// Reads a string stored with the given [`StringLayout`] and [`TextEncoding`].
+    ///
+    /// Same semantics as [`DataParser::get_encoded_string`].
+    ///
+    /// [`StringLayout`]: crate::options::StringLayout
+    /// [`TextEncoding`]: crate::text::TextEncoding
+    /// [`DataParser::get_encoded_string`]: crate::parser::core::DataParser::get_encoded_string
+    pub fn get_encoded_string(
+        &mut self,
+        layout: StringLayout,
+        encoding: TextEncoding,
+    ) -> ParseResult<String> {
+        match layout {
+            StringLayout::LengthPrefixed => {
+                let str_len = self.get_length(self.options.length_prefix)?;
+                self.check_limit(Limit::StringLength, str_len)?;
+                let bytes = self.get_bytes(str_len)?;
+                self._get_string(&bytes, encoding)
+            }
+            StringLayout::NullTerminated => {
+                let unit = encoding.unit_size();
+                let mut string_buf = Vec::new();
+                let mut code_unit = [0u8; 4];
+                loop {
+                    let read = read_up_to(&mut self.reader, &mut code_unit[..unit])?;
+                    if read < unit || code_unit[..unit].iter().all(|&b| b == 0) {
+                        break;
+                    }
+                    string_buf.extend_from_slice(&code_unit[..unit]);
+                    self.check_limit(Limit::StringLength, string_buf.len())?;
+                }
+                self.reserve(string_buf.len())?;
+                self._get_string(&string_buf, encoding)
+            }
+            StringLayout::FixedWidth { width, pad } => {
+                self.check_limit(Limit::StringLength, width)?;
+                let bytes = self.get_bytes(width)?;
+                let len = strip_padding(&bytes, pad, encoding.unit_size());
+                self._get_string(&bytes[..len], encoding)
+            }
+            StringLayout::RestOfInput => {
+                let mut bytes = Vec::new();
+                match self.options.max_string_len {
+                    // Read one byte past the limit, just enough to detect a longer string.
+                    Some(max) => (&mut self.reader)
+                        .take((max as u64).saturating_add(1))
+                        .read_to_end(&mut bytes)?,
+                    None => self.reader.read_to_end(&mut bytes)?,
+                };
+                self.check_limit(Limit::StringLength, bytes.len())?;
+                self.reserve(bytes.len())?;
+                self._get_string(&bytes, encoding)
+            }
+        }
+    }
+}
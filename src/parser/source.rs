@@ -0,0 +1,220 @@
+use std::io::Read;
+
+#[cfg(feature = "async")]
+use crate::parser::readers::async_reader::{core::AsyncDataReader, helpers::AsyncDecodable};
+use crate::{
+    impl_byte_source, impl_source_decodable,
+    options::{LengthPrefix, ParseOptions, StringLayout},
+    parser::{
+        core::DataParser,
+        helpers::Decodable,
+        readers::sync_reader::{core::DataReader, helpers::StreamDecodable},
+    },
+    text::TextEncoding,
+    utils::ParseResult,
+};
+
+/// A source of encoded bytes that values can be decoded from.
+///
+/// `ByteSource` is implemented by [`DataParser`] (in-memory buffers), [`DataReader`]
+/// (blocking streams) and, with the `async` feature, `AsyncDataReader` (async streams).
+/// Every method behaves exactly like the inherent method of the same name on
+/// [`DataParser`], so the same bytes decode to the same values whatever the source is.
+///
+/// [`DataReader`]: crate::parser::readers::sync_reader::core::DataReader
+pub trait ByteSource {
+    /// Returns the options the source decodes with.
+    fn options(&self) -> &ParseOptions;
+
+    /// Runs `f` one nesting level deeper, enforcing [`max_depth`].
+    ///
+    /// [`max_depth`]: crate::options::ParseOptions::set_max_depth
+    fn nested<T, F>(&mut self, f: F) -> ParseResult<T>
+    where
+        F: FnOnce(&mut Self) -> ParseResult<T>;
+
+    /// Reads the next `n` bytes.
+    fn get_bytes(&mut self, n: usize) -> ParseResult<Vec<u8>>;
+
+    /// Reads a single byte.
+    fn get_byte(&mut self) -> ParseResult<u8>;
+
+    /// Reads a byte and interprets any non-zero value as `true`.
+    fn get_bool(&mut self) -> ParseResult<bool>;
+
+    /// Reads a length prefix of the given kind.
+    fn get_length(&mut self, prefix: LengthPrefix) -> ParseResult<usize>;
+
+    /// Reads an unsigned integer that is `n_bytes` wide (1 to 8).
+    fn get_uint(&mut self, n_bytes: usize) -> ParseResult<u64>;
+
+    /// Reads a signed integer that is `n_bytes` wide (1 to 8), sign-extended to an `i64`.
+    fn get_int(&mut self, n_bytes: usize) -> ParseResult<i64>;
+
+    fn get_u8(&mut self) -> ParseResult<u8>;
+    fn get_u16(&mut self) -> ParseResult<u16>;
+    fn get_u32(&mut self) -> ParseResult<u32>;
+    fn get_u64(&mut self) -> ParseResult<u64>;
+    fn get_u128(&mut self) -> ParseResult<u128>;
+    fn get_usize(&mut self) -> ParseResult<usize>;
+    fn get_i8(&mut self) -> ParseResult<i8>;
+    fn get_i16(&mut self) -> ParseResult<i16>;
+    fn get_i32(&mut self) -> ParseResult<i32>;
+    fn get_i64(&mut self) -> ParseResult<i64>;
+    fn get_i128(&mut self) -> ParseResult<i128>;
+    fn get_isize(&mut self) -> ParseResult<isize>;
+    fn get_f32(&mut self) -> ParseResult<f32>;
+    fn get_f64(&mut self) -> ParseResult<f64>;
+
+    fn get_leb128_u8(&mut self) -> ParseResult<u8>;
+    fn get_leb128_u16(&mut self) -> ParseResult<u16>;
+    fn get_leb128_u32(&mut self) -> ParseResult<u32>;
+    fn get_leb128_u64(&mut self) -> ParseResult<u64>;
+    fn get_leb128_u128(&mut self) -> ParseResult<u128>;
+    fn get_leb128_usize(&mut self) -> ParseResult<usize>;
+    fn get_leb128_i8(&mut self) -> ParseResult<i8>;
+    fn get_leb128_i16(&mut self) -> ParseResult<i16>;
+    fn get_leb128_i32(&mut self) -> ParseResult<i32>;
+    fn get_leb128_i64(&mut self) -> ParseResult<i64>;
+    fn get_leb128_i128(&mut self) -> ParseResult<i128>;
+    fn get_leb128_isize(&mut self) -> ParseResult<isize>;
+    fn get_zigzag_i8(&mut self) -> ParseResult<i8>;
+    fn get_zigzag_i16(&mut self) -> ParseResult<i16>;
+    fn get_zigzag_i32(&mut self) -> ParseResult<i32>;
+    fn get_zigzag_i64(&mut self) -> ParseResult<i64>;
+    fn get_zigzag_i128(&mut self) -> ParseResult<i128>;
+    fn get_zigzag_isize(&mut self) -> ParseResult<isize>;
+
+    /// Reads a length-prefixed string, as UTF-16 if `utf16` is set.
+    fn get_string(&mut self, utf16: bool) -> ParseResult<String>;
+
+    /// Reads a string like [`get_string`](ByteSource::get_string), using `prefix` instead of
+    /// the configured length prefix.
+    fn get_string_with_prefix(&mut self, utf16: bool, prefix: LengthPrefix) -> ParseResult<String>;
+
+    /// Reads a string stored with the given [`StringLayout`].
+    fn get_string_with_layout(&mut self, layout: StringLayout, utf16: bool) -> ParseResult<String>;
+
+    /// Reads a string stored with the given [`StringLayout`] and [`TextEncoding`].
+    fn get_encoded_string(
+        &mut self,
+        layout: StringLayout,
+        encoding: TextEncoding,
+    ) -> ParseResult<String>;
+
+    /// Reads a count-prefixed vector whose elements are each wrapped in a length-prefixed section.
+    fn get_vector<T: SourceDecodable>(&mut self) -> ParseResult<Vec<T>>;
+
+    /// Reads a vector like [`get_vector`](ByteSource::get_vector), using `prefix` instead of
+    /// the configured length prefix.
+    fn get_vector_with_prefix<T: SourceDecodable>(
+        &mut self,
+        prefix: LengthPrefix,
+    ) -> ParseResult<Vec<T>>;
+
+    /// Reads a presence flag, followed by the value if the flag is set.
+    fn get_option<T: SourceDecodable>(&mut self) -> ParseResult<Option<T>>;
+}
+
+/// A type that can be decoded from any [`ByteSource`].
+///
+/// Implementing this trait once makes a type [`Decodable`], [`StreamDecodable`] and (with the
+/// `async` feature) `AsyncDecodable`, so it can be read from a buffer, a blocking stream, or an
+/// async stream with identical results.
+/// Prefer it over implementing those traits directly.
+///
+/// `Vec<T>` and `Option<T>` aren't `SourceDecodable` themselves, so that they stay
+/// [`Decodable`] for element types that only implement `Decodable`. Inside `from_source`,
+/// read them with [`ByteSource::get_vector`] and [`ByteSource::get_option`].
+///
+/// # Example
+/// ```rust
+/// use std::io::Cursor;
+/// use dataparser_core::{
+///     ByteSource, DataParser, Decodable, ParseResult, SourceDecodable, StreamDecodable,
+///     parser::readers::sync_reader::core::DataReader,
+/// };
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Entry {
+///     id: u16,
+///     name: String,
+///     enabled: bool,
+/// }
+///
+/// impl SourceDecodable for Entry {
+///     fn from_source<S: ByteSource>(source: &mut S) -> ParseResult<Self> {
+///         Ok(Entry {
+///             id: source.get_u16()?,
+///             name: source.get_string(false)?,
+///             enabled: source.get_bool()?,
+///         })
+///     }
+/// }
+///
+/// let bytes = [0x00, 0x07, 0x00, 0x00, 0x00, 0x02, b'o', b'k', 0x01];
+/// let from_buffer = Entry::from_parser(&mut DataParser::new(&bytes))?;
+/// let from_stream = Entry::from_stream_parser(&mut DataReader::new(Cursor::new(bytes)))?;
+/// assert_eq!(from_buffer, from_stream);
+/// assert_eq!(from_buffer.name, "ok");
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+pub trait SourceDecodable: Sized {
+    fn from_source<S: ByteSource>(source: &mut S) -> ParseResult<Self>;
+}
+
+impl<T: SourceDecodable> Decodable for T {
+    fn from_parser(parser: &mut DataParser) -> ParseResult<Self> {
+        T::from_source(parser)
+    }
+}
+
+impl<T: SourceDecodable> StreamDecodable for T {
+    fn from_stream_parser<R: Read>(parser: &mut DataReader<R>) -> ParseResult<Self> {
+        T::from_source(parser)
+    }
+}
+
+impl ByteSource for DataParser<'_> {
+    impl_byte_source!();
+}
+
+impl<R: Read> ByteSource for DataReader<R> {
+    impl_byte_source!();
+}
+
+/// Reads the bytes the reader has buffered, so a value decoded through it must be decoded
+/// with [`AsyncDecodable`], which reads more from the stream and starts over whenever the
+/// buffered bytes run out.
+///
+/// [`AsyncDecodable`]: crate::parser::readers::async_reader::helpers::AsyncDecodable
+#[cfg(feature = "async")]
+impl<R: tokio::io::AsyncRead + Unpin> ByteSource for AsyncDataReader<R> {
+    impl_byte_source!(buffered);
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl<T: SourceDecodable + Send> AsyncDecodable for T {
+    async fn from_async_reader<R: tokio::io::AsyncRead + Unpin + Send>(
+        reader: &mut AsyncDataReader<R>,
+    ) -> ParseResult<Self> {
+        reader.read_buffered(T::from_source).await
+    }
+}
+
+impl SourceDecodable for bool {
+    fn from_source<S: ByteSource>(source: &mut S) -> ParseResult<Self> {
+        source.get_bool()
+    }
+}
+
+impl SourceDecodable for String {
+    fn from_source<S: ByteSource>(source: &mut S) -> ParseResult<Self> {
+        source.get_string(false)
+    }
+}
+
+impl_source_decodable!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64
+);
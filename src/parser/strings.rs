@@ -1,107 +1,143 @@
-use crate::{errors::DataParseError, utils::ParseResult};
+use crate::{
+    errors::DataParseError,
+    options::{LengthPrefix, Limit, StringLayout},
+    text::TextEncoding,
+    utils::ParseResult,
+};
 
 use super::core::DataParser;
 
-impl DataParser<'_> {
-    fn _get_string(&mut self, str_len: usize, utf16: bool) -> ParseResult<String> {
+/// Decodes `bytes` with the given text encoding.
+///
+/// With `strict` set, invalid input is an error; otherwise it is replaced lossily.
+/// With `trim_nulls` set, trailing `\0` characters are removed from the result.
+pub(crate) fn decode_string(
+    bytes: &[u8],
+    encoding: TextEncoding,
+    strict: bool,
+    trim_nulls: bool,
+) -> ParseResult<String> {
+    let mut string = encoding.decode(bytes, strict)?;
+    if trim_nulls {
+        string = string.trim_end_matches('\0').to_string();
+    }
+    Ok(string)
+}
+
+/// Returns the length of `bytes` once trailing `pad` units are stripped.
+///
+/// A unit is `unit_size` copies of `pad` (e.g. two bytes for UTF-16), so a padded field
+/// never splits a code unit.
+pub(crate) fn strip_padding(bytes: &[u8], pad: u8, unit_size: usize) -> usize {
+    let mut len = bytes.len() - bytes.len() % unit_size;
+    while len >= unit_size && bytes[len - unit_size..len].iter().all(|&b| b == pad) {
+        len -= unit_size;
+    }
+    len
+}
+
+impl<'a> DataParser<'a> {
+    fn _get_string(&mut self, str_len: usize, encoding: TextEncoding) -> ParseResult<String> {
         let strict = self.options.strict_encoding;
         let trim_nulls = self.options.trim_null_strings;
+        self.check_limit(Limit::StringLength, str_len)?;
+        self.reserve(str_len)?;
         let bytes = self.take(str_len)?;
-        if utf16 {
-            if bytes.len() % 2 != 0 {
-                return Err(DataParseError::InvalidConversion {
-                    e: "UTF-16 input length must be even".into(),
-                });
-            }
-
-            let utf_16_vec: Vec<u16> = bytes
-                .chunks_exact(2)
-                .map(|pair| u16::from_ne_bytes([pair[0], pair[1]]))
-                .collect();
-
-            let mut string = if strict {
-                String::from_utf16(&utf_16_vec)
-                    .map_err(|e| DataParseError::InvalidConversion { e: e.to_string() })?
-            } else {
-                String::from_utf16_lossy(&utf_16_vec)
-            };
-
-            if trim_nulls {
-                string = string.trim_end_matches('\0').to_string();
-            }
-
-            Ok(string)
-        } else {
-            let mut string = if strict {
-                std::str::from_utf8(bytes)
-                    .map_err(|e| DataParseError::InvalidConversion { e: e.to_string() })?
-                    .to_string()
-            } else {
-                String::from_utf8_lossy(bytes).into()
-            };
+        decode_string(bytes, encoding, strict, trim_nulls)
+    }
 
-            if trim_nulls {
-                string = string.trim_end_matches('\0').to_string();
+    fn _get_string_raw(&mut self, encoding: TextEncoding) -> ParseResult<String> {
+        let unit = encoding.unit_size();
+        let mut string_buf = Vec::new();
+        while self.remaining() >= unit {
+            let bytes = self.take(unit)?;
+            if bytes.iter().all(|&b| b == 0) {
+                break;
             }
-
-            Ok(string)
+            string_buf.extend_from_slice(bytes);
+            self.check_limit(Limit::StringLength, string_buf.len())?;
         }
+        self.reserve(string_buf.len())?;
+        decode_string(
+            &string_buf,
+            encoding,
+            self.options.strict_encoding,
+            self.options.trim_null_strings,
+        )
     }
 
-    fn _get_string_raw(&mut self, utf16: bool) -> ParseResult<String> {
-        if utf16 {
-            let mut utf16_buf = Vec::new();
-            while self.remaining() >= 2 {
-                let bytes = self.take(2)?;
-                let val = u16::from_ne_bytes([bytes[0], bytes[1]]);
-                if val == 0 {
-                    break;
-                }
-                utf16_buf.push(val);
-            }
-            let mut s = String::from_utf16_lossy(&utf16_buf);
-            if self.options.trim_null_strings {
-                s = s.trim_end_matches('\0').to_string();
-            }
-            Ok(s)
-        } else {
-            let mut string_buf = Vec::new();
-            while self.remaining() > 0 {
-                let byte = self.get_byte()?;
-                if byte == 0 {
-                    break;
-                }
-                string_buf.push(byte);
-            }
-
-            let result = if self.options.strict_encoding {
-                String::from_utf8(string_buf)
-                    .map_err(|e| DataParseError::InvalidConversion { e: e.to_string() })?
-            } else {
-                String::from_utf8_lossy(&string_buf).to_string()
-            };
+    fn _get_string_fixed(
+        &mut self,
+        width: usize,
+        pad: u8,
+        encoding: TextEncoding,
+    ) -> ParseResult<String> {
+        let strict = self.options.strict_encoding;
+        let trim_nulls = self.options.trim_null_strings;
+        self.check_limit(Limit::StringLength, width)?;
+        self.reserve(width)?;
+        let bytes = self.take(width)?;
+        let len = strip_padding(bytes, pad, encoding.unit_size());
+        decode_string(&bytes[..len], encoding, strict, trim_nulls)
+    }
 
-            Ok(result)
+    /// Parses a length-prefixed UTF-8 string and returns it borrowed from the input buffer.
+    ///
+    /// The format matches [`get_string`], but no allocation is made: the returned `&'a str`
+    /// points straight into the input. Since lossy conversion would require allocating,
+    /// invalid UTF-8 is always an error here, regardless of [`strict_encoding`].
+    /// [`trim_null_strings`] is still honored.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - Not enough bytes are available
+    /// - The bytes are not valid UTF-8
+    /// - The parser owns its buffer (see [`get_slice`])
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::parser::DataParser;
+    /// let data: &[u8] = &[0x00, 0x00, 0x00, 0x02, b'h', b'i'];
+    /// let mut parser = DataParser::new(data);
+    /// assert_eq!(parser.get_str_borrowed().unwrap(), "hi");
+    /// ```
+    ///
+    /// [`get_string`]: DataParser::get_string
+    /// [`get_slice`]: DataParser::get_slice
+    /// [`strict_encoding`]: crate::options::ParseOptions
+    /// [`trim_null_strings`]: crate::options::ParseOptions
+    pub fn get_str_borrowed(&mut self) -> ParseResult<&'a str> {
+        let str_len = self.get_length(self.options.length_prefix)?;
+        self.check_limit(Limit::StringLength, str_len)?;
+        let bytes = self.get_slice(str_len)?;
+        let string = std::str::from_utf8(bytes)
+            .map_err(|e| DataParseError::InvalidConversion { e: e.to_string() })?;
+        if self.options.trim_null_strings {
+            Ok(string.trim_end_matches('\0'))
+        } else {
+            Ok(string)
         }
     }
 
     /// Parses a length-prefixed string from the input stream.
     ///
-    /// This method expects a `u32` length prefix followed by a UTF-8 or UTF-16 encoded string.
+    /// This method expects a length prefix (a `u32` unless configured otherwise through
+    /// [`length_prefix`]) followed by the encoded string bytes.
     /// The parsing behavior can be adjusted via the following options:
     ///
     /// - [`strict_encoding`]: If `true`, parsing will return an error on invalid encoding.
     /// - [`trim_null_strings`]: If `true`, trailing null bytes (`\0`) will be removed.
     ///
     /// # Parameters
-    /// - `utf16`: If `true`, the string is parsed as UTF-16. Otherwise, UTF-8 is used.
+    /// - `utf16`: If `true`, the string is parsed as UTF-16 in the configured `endianness`.
+    ///   Otherwise, the configured [`text_encoding`] is used (UTF-8 by default).
     ///
     /// # Returns
     /// A `String` if parsing succeeds, or a [`DataParseError`] on failure.
     ///
     /// # Format
-    /// ```
-    /// [length: u32][string_bytes...]
+    /// ```text
+    /// [length: prefix][string_bytes...]
     /// ```
     ///
     /// # Errors
@@ -111,16 +147,88 @@ impl DataParser<'_> {
     ///
     /// # Example
     /// ```rust
-    /// let mut parser = DataParser::new(Cursor::new(vec![0x05, 0x00, 0x00, 0x00, b'H', b'e', b'l', b'l', b'o']));
+    /// # use dataparser_core::DataParser;
+    /// let mut parser = DataParser::new(vec![0x00, 0x00, 0x00, 0x05, b'H', b'e', b'l', b'l', b'o']);
     /// let s = parser.get_string(false).unwrap();
     /// assert_eq!(s, "Hello");
     /// ```
     ///
     /// [`strict_encoding`]: crate::options::ParseOptions
     /// [`trim_null_strings`]: crate::options::ParseOptions
+    /// [`length_prefix`]: crate::options::LengthPrefix
+    /// [`text_encoding`]: crate::text::TextEncoding
     pub fn get_string(&mut self, utf16: bool) -> ParseResult<String> {
-        let str_len = self.get_u32()?;
+        self.get_string_with_prefix(utf16, self.options.length_prefix)
+    }
+
+    /// Parses a string like [`get_string`], using `prefix` instead of the configured length prefix.
+    ///
+    /// [`get_string`]: DataParser::get_string
+    pub fn get_string_with_prefix(
+        &mut self,
+        utf16: bool,
+        prefix: LengthPrefix,
+    ) -> ParseResult<String> {
+        let encoding = self.options.string_encoding(utf16);
+        let str_len = self.get_length(prefix)?;
         // Strings also have to prepend the size to the data
-        self._get_string(str_len as usize, utf16)
+        self._get_string(str_len, encoding)
+    }
+
+    /// Parses a string stored with the given [`StringLayout`].
+    ///
+    /// - `LengthPrefixed`: same as [`get_string`]
+    /// - `NullTerminated`: reads up to (and consumes) a null terminator, or to the end of input
+    /// - `FixedWidth`: reads exactly `width` bytes and strips trailing `pad` bytes
+    /// - `RestOfInput`: reads every remaining byte
+    ///
+    /// `utf16` has the same meaning as in [`get_string`]; use [`get_encoded_string`] to pick
+    /// any other text encoding. [`strict_encoding`] and [`trim_null_strings`] are honored
+    /// for every layout.
+    ///
+    /// # Example
+    /// ```rust
+    /// use dataparser_core::{DataParser, options::StringLayout};
+    /// let mut parser = DataParser::new(b"abc\0de  xyz");
+    /// assert_eq!(parser.get_string_with_layout(StringLayout::NullTerminated, false)?, "abc");
+    /// let fixed = StringLayout::FixedWidth { width: 4, pad: b' ' };
+    /// assert_eq!(parser.get_string_with_layout(fixed, false)?, "de");
+    /// assert_eq!(parser.get_string_with_layout(StringLayout::RestOfInput, false)?, "xyz");
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    ///
+    /// [`get_string`]: DataParser::get_string
+    /// [`get_encoded_string`]: DataParser::get_encoded_string
+    /// [`strict_encoding`]: crate::options::ParseOptions
+    /// [`trim_null_strings`]: crate::options::ParseOptions
+    pub fn get_string_with_layout(
+        &mut self,
+        layout: StringLayout,
+        utf16: bool,
+    ) -> ParseResult<String> {
+        self.get_encoded_string(layout, self.options.string_encoding(utf16))
+    }
+
+    /// Parses a string stored with the given [`StringLayout`] and [`TextEncoding`].
+    ///
+    /// In a fixed-width field, padding is stripped one code unit at a time, and a
+    /// null terminator is one code unit wide (e.g. two bytes for UTF-16).
+    ///
+    /// [`StringLayout`]: crate::options::StringLayout
+    /// [`TextEncoding`]: crate::text::TextEncoding
+    pub fn get_encoded_string(
+        &mut self,
+        layout: StringLayout,
+        encoding: TextEncoding,
+    ) -> ParseResult<String> {
+        match layout {
+            StringLayout::LengthPrefixed => {
+                let str_len = self.get_length(self.options.length_prefix)?;
+                self._get_string(str_len, encoding)
+            }
+            StringLayout::NullTerminated => self._get_string_raw(encoding),
+            StringLayout::FixedWidth { width, pad } => self._get_string_fixed(width, pad, encoding),
+            StringLayout::RestOfInput => self._get_string(self.remaining(), encoding),
+        }
     }
 }
@@ -0,0 +1,361 @@
+//! Text encodings supported for string fields.
+//!
+//! [`TextEncoding`] selects how string bytes are interpreted by the parsers and produced by
+//! the encoders. It can be configured through [`ParseOptions`] / [`EncodingOptions`] or
+//! passed per call to `get_encoded_string` / `add_encoded_string`.
+//!
+//! Invalid input (or, when encoding, characters the target encoding can't represent) is an
+//! error under `strict_encoding`. Otherwise it is replaced: with `U+FFFD` when decoding, and
+//! with `?` when encoding into a single-byte encoding.
+//!
+//! [`ParseOptions`]: crate::options::ParseOptions
+//! [`EncodingOptions`]: crate::options::EncodingOptions
+use crate::errors::DataParseError;
+use crate::utils::ParseResult;
+
+/// A character encoding for string data.
+///
+/// # Example
+/// ```
+/// use dataparser_core::{DataEncoder, DataParser, options::StringLayout, text::TextEncoding};
+/// let mut encoder = DataEncoder::new();
+/// encoder.add_encoded_string("hé", StringLayout::NullTerminated, TextEncoding::Utf16Le)?;
+/// assert_eq!(encoder.get_data()?, &vec![b'h', 0, 0xE9, 0, 0, 0]);
+///
+/// let mut parser = DataParser::new(encoder.get_data()?);
+/// let s = parser.get_encoded_string(StringLayout::NullTerminated, TextEncoding::Utf16Le)?;
+/// assert_eq!(s, "hé");
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextEncoding {
+    /// UTF-8 (default).
+    #[default]
+    Utf8,
+    /// UTF-16, little-endian.
+    Utf16Le,
+    /// UTF-16, big-endian.
+    Utf16Be,
+    /// UTF-16 with a byte order mark.
+    ///
+    /// When decoding, a leading BOM selects the byte order and is stripped; without one,
+    /// big-endian is assumed. When encoding, a big-endian BOM is written first.
+    Utf16,
+    /// UTF-32, little-endian.
+    Utf32Le,
+    /// UTF-32, big-endian.
+    Utf32Be,
+    /// 7-bit ASCII. Bytes above `0x7F` are invalid.
+    Ascii,
+    /// ISO-8859-1, mapping each byte to the code point of the same value.
+    Latin1,
+    /// Windows code page 1252.
+    Windows1252,
+}
+
+/// Characters for bytes `0x80..=0x9F` in Windows-1252; `None` marks unassigned bytes.
+const WINDOWS_1252_HIGH: [Option<char>; 32] = [
+    Some('\u{20AC}'),
+    None,
+    Some('\u{201A}'),
+    Some('\u{0192}'),
+    Some('\u{201E}'),
+    Some('\u{2026}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{02C6}'),
+    Some('\u{2030}'),
+    Some('\u{0160}'),
+    Some('\u{2039}'),
+    Some('\u{0152}'),
+    None,
+    Some('\u{017D}'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('\u{2022}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{02DC}'),
+    Some('\u{2122}'),
+    Some('\u{0161}'),
+    Some('\u{203A}'),
+    Some('\u{0153}'),
+    None,
+    Some('\u{017E}'),
+    Some('\u{0178}'),
+];
+
+fn invalid(encoding: TextEncoding, detail: String) -> DataParseError {
+    DataParseError::InvalidConversion {
+        e: format!("Invalid {:?} data: {}", encoding, detail),
+    }
+}
+
+impl TextEncoding {
+    /// Returns the size in bytes of one code unit, which is also the size of a null terminator.
+    pub fn unit_size(&self) -> usize {
+        match self {
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be | TextEncoding::Utf16 => 2,
+            TextEncoding::Utf32Le | TextEncoding::Utf32Be => 4,
+            _ => 1,
+        }
+    }
+
+    /// Decodes `bytes` into a `String`.
+    ///
+    /// A trailing partial code unit (when the length of `bytes` isn't a multiple of
+    /// [`unit_size`](TextEncoding::unit_size)) is decoded as `U+FFFD`, like any other
+    /// invalid input.
+    ///
+    /// # Errors
+    /// With `strict` set, returns an error on any invalid input, including a trailing partial
+    /// code unit.
+    ///
+    /// # Example
+    /// ```
+    /// use dataparser_core::text::TextEncoding;
+    /// assert_eq!(TextEncoding::Utf16Le.decode(&[b'h', 0, b'i'], false)?, "h\u{FFFD}");
+    /// assert!(TextEncoding::Utf16Le.decode(&[b'h', 0, b'i'], true).is_err());
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    pub fn decode(&self, bytes: &[u8], strict: bool) -> ParseResult<String> {
+        let partial = bytes.len() % self.unit_size();
+        if partial == 0 {
+            return self.decode_units(bytes, strict);
+        }
+        if strict {
+            return Err(invalid(
+                *self,
+                format!(
+                    "input length {} is not a multiple of {}",
+                    bytes.len(),
+                    self.unit_size()
+                ),
+            ));
+        }
+        let mut decoded = self.decode_units(&bytes[..bytes.len() - partial], strict)?;
+        decoded.push(char::REPLACEMENT_CHARACTER);
+        Ok(decoded)
+    }
+
+    /// Decodes `bytes`, whose length is a multiple of the unit size.
+    fn decode_units(&self, bytes: &[u8], strict: bool) -> ParseResult<String> {
+        match self {
+            TextEncoding::Utf8 => {
+                if strict {
+                    std::str::from_utf8(bytes)
+                        .map(str::to_string)
+                        .map_err(|e| DataParseError::InvalidConversion { e: e.to_string() })
+                } else {
+                    Ok(String::from_utf8_lossy(bytes).into())
+                }
+            }
+            TextEncoding::Utf16Le => decode_utf16(bytes, false, strict),
+            TextEncoding::Utf16Be => decode_utf16(bytes, true, strict),
+            TextEncoding::Utf16 => match bytes {
+                [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, false, strict),
+                [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, true, strict),
+                _ => decode_utf16(bytes, true, strict),
+            },
+            TextEncoding::Utf32Le | TextEncoding::Utf32Be => {
+                let big_endian = *self == TextEncoding::Utf32Be;
+                bytes
+                    .chunks_exact(4)
+                    .map(|unit| {
+                        let unit = [unit[0], unit[1], unit[2], unit[3]];
+                        let value = if big_endian {
+                            u32::from_be_bytes(unit)
+                        } else {
+                            u32::from_le_bytes(unit)
+                        };
+                        match char::from_u32(value) {
+                            Some(c) => Ok(c),
+                            None if strict => {
+                                Err(invalid(*self, format!("{:#x} is not a code point", value)))
+                            }
+                            None => Ok(char::REPLACEMENT_CHARACTER),
+                        }
+                    })
+                    .collect()
+            }
+            TextEncoding::Ascii => bytes
+                .iter()
+                .map(|&b| match b {
+                    0..=0x7F => Ok(b as char),
+                    _ if strict => Err(invalid(*self, format!("byte {:#x} is not ASCII", b))),
+                    _ => Ok(char::REPLACEMENT_CHARACTER),
+                })
+                .collect(),
+            TextEncoding::Latin1 => Ok(bytes.iter().map(|&b| b as char).collect()),
+            TextEncoding::Windows1252 => bytes
+                .iter()
+                .map(|&b| match b {
+                    0x80..=0x9F => match WINDOWS_1252_HIGH[(b - 0x80) as usize] {
+                        Some(c) => Ok(c),
+                        None if strict => {
+                            Err(invalid(*self, format!("byte {:#x} is unassigned", b)))
+                        }
+                        None => Ok(char::REPLACEMENT_CHARACTER),
+                    },
+                    _ => Ok(b as char),
+                })
+                .collect(),
+        }
+    }
+
+    /// Encodes `data` into bytes.
+    ///
+    /// # Errors
+    /// With `strict` set, returns an error if `data` contains a character that can't be
+    /// represented. Otherwise such characters are written as `?`.
+    pub fn encode(&self, data: &str, strict: bool) -> ParseResult<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(data.len());
+        self.encode_with(data, strict, |chunk| {
+            bytes.extend_from_slice(chunk);
+            Ok(())
+        })?;
+        Ok(bytes)
+    }
+
+    /// Returns the number of bytes [`encode`](TextEncoding::encode) produces for `data`,
+    /// without allocating.
+    ///
+    /// # Errors
+    /// Fails exactly when `encode` does.
+    ///
+    /// # Example
+    /// ```
+    /// use dataparser_core::text::TextEncoding;
+    /// assert_eq!(TextEncoding::Utf8.encoded_len("hé", true)?, 3);
+    /// assert_eq!(TextEncoding::Utf16.encoded_len("hé", true)?, 6);
+    /// assert!(TextEncoding::Ascii.encoded_len("hé", true).is_err());
+    /// # Ok::<(), dataparser_core::DataParseError>(())
+    /// ```
+    pub fn encoded_len(&self, data: &str, strict: bool) -> ParseResult<usize> {
+        let mut len = 0;
+        self.encode_with(data, strict, |chunk| {
+            len += chunk.len();
+            Ok(())
+        })?;
+        Ok(len)
+    }
+
+    /// Encodes `data`, handing the bytes to `write` in chunks instead of collecting them.
+    pub(crate) fn encode_with(
+        &self,
+        data: &str,
+        strict: bool,
+        mut write: impl FnMut(&[u8]) -> ParseResult<()>,
+    ) -> ParseResult<()> {
+        if *self == TextEncoding::Utf8 {
+            return write(data.as_bytes());
+        }
+        let mut chunk = [0u8; 256];
+        let mut used = 0;
+        if *self == TextEncoding::Utf16 {
+            chunk[..2].copy_from_slice(&[0xFE, 0xFF]);
+            used = 2;
+        }
+        for c in data.chars() {
+            let mut scratch = [0u8; 4];
+            let unit = self.encode_char(c, strict, &mut scratch)?;
+            if used + unit.len() > chunk.len() {
+                write(&chunk[..used])?;
+                used = 0;
+            }
+            chunk[used..used + unit.len()].copy_from_slice(unit);
+            used += unit.len();
+        }
+        if used > 0 {
+            write(&chunk[..used])?;
+        }
+        Ok(())
+    }
+
+    /// Encodes a single character into `scratch` and returns its bytes.
+    fn encode_char<'s>(
+        &self,
+        c: char,
+        strict: bool,
+        scratch: &'s mut [u8; 4],
+    ) -> ParseResult<&'s [u8]> {
+        match self {
+            TextEncoding::Utf8 => Ok(c.encode_utf8(scratch).as_bytes()),
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be | TextEncoding::Utf16 => {
+                let mut units = [0u16; 2];
+                let units = c.encode_utf16(&mut units);
+                for (i, unit) in units.iter().enumerate() {
+                    let bytes = if *self == TextEncoding::Utf16Le {
+                        unit.to_le_bytes()
+                    } else {
+                        unit.to_be_bytes()
+                    };
+                    scratch[2 * i..2 * i + 2].copy_from_slice(&bytes);
+                }
+                Ok(&scratch[..2 * units.len()])
+            }
+            TextEncoding::Utf32Le => {
+                *scratch = (c as u32).to_le_bytes();
+                Ok(scratch)
+            }
+            TextEncoding::Utf32Be => {
+                *scratch = (c as u32).to_be_bytes();
+                Ok(scratch)
+            }
+            TextEncoding::Ascii | TextEncoding::Latin1 | TextEncoding::Windows1252 => {
+                scratch[0] = match self.encode_single_byte(c) {
+                    Some(b) => b,
+                    None if strict => {
+                        return Err(invalid(
+                            *self,
+                            format!("character {:?} can't be represented", c),
+                        ));
+                    }
+                    None => b'?',
+                };
+                Ok(&scratch[..1])
+            }
+        }
+    }
+
+    /// Maps a character onto its byte in a single-byte encoding.
+    fn encode_single_byte(&self, c: char) -> Option<u8> {
+        let code = c as u32;
+        match self {
+            TextEncoding::Ascii if code <= 0x7F => Some(code as u8),
+            TextEncoding::Latin1 if code <= 0xFF => Some(code as u8),
+            TextEncoding::Windows1252 => match code {
+                0x80..=0x9F => None,
+                0..=0xFF => Some(code as u8),
+                _ => WINDOWS_1252_HIGH
+                    .iter()
+                    .position(|&high| high == Some(c))
+                    .map(|i| 0x80 + i as u8),
+            },
+            _ => None,
+        }
+    }
+}
+
+fn decode_utf16(bytes: &[u8], big_endian: bool, strict: bool) -> ParseResult<String> {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| {
+            if big_endian {
+                u16::from_be_bytes([pair[0], pair[1]])
+            } else {
+                u16::from_le_bytes([pair[0], pair[1]])
+            }
+        })
+        .collect();
+    if strict {
+        String::from_utf16(&units)
+            .map_err(|e| DataParseError::InvalidConversion { e: e.to_string() })
+    } else {
+        Ok(String::from_utf16_lossy(&units))
+    }
+}
@@ -7,7 +7,10 @@ use crate::errors::DataParseError;
 ///
 /// # Example
 /// ```
-/// fn parse_u32(input: &[u8]) -> ParseResult<u32> { ... }
+/// # use dataparser_core::{DataParser, ParseResult};
+/// fn parse_u32(input: &[u8]) -> ParseResult<u32> {
+///     DataParser::new(input).get_u32()
+/// }
 /// ```
 ///
 /// [`DataParseError`]: crate::errors::DataParseError
@@ -24,6 +27,7 @@ pub type ParseResult<T> = Result<T, DataParseError>;
 ///
 /// # Example
 /// ```
+/// use dataparser_core::utils::{EndianSerialize, Endianness};
 /// let endianness = Endianness::LittleEndian;
 /// let bytes = 42u32.to_endian_bytes(&endianness);
 /// ```
@@ -43,18 +47,50 @@ pub enum Endianness {
     NativeEndian,
 }
 
+/// Represents the order in which bits are packed into each byte by [`BitParser`] and [`BitWriter`].
+///
+/// - `MsbFirst`: The first bit of a field is the most significant bit of the byte (default).
+///   This is the layout used by most network headers (e.g. IPv4 flags).
+/// - `LsbFirst`: The first bit of a field is the least significant bit of the byte,
+///   as used by DEFLATE and many codec bitstreams.
+///
+/// [`BitParser`]: crate::parser::bits::BitParser
+/// [`BitWriter`]: crate::encoder::bits::BitWriter
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+    /// Most significant bit first.
+    #[default]
+    MsbFirst,
+
+    /// Least significant bit first.
+    LsbFirst,
+}
+
 /// A trait for converting numeric types to their byte representation with a given endianness.
 ///
 /// Used by encoding APIs to abstract away byte order concerns.
 ///
 /// # Example
 /// ```
-/// use dataparser_core::Endianness;
+/// use dataparser_core::utils::{EndianSerialize, Endianness};
 /// let n: u32 = 0x12345678;
 /// let bytes = n.to_endian_bytes(&Endianness::LittleEndian);
 /// ```
 pub trait EndianSerialize {
     fn to_endian_bytes(self, endianness: &Endianness) -> Vec<u8>;
+
+    /// Writes the byte representation to `out`, which must be exactly as long as it.
+    ///
+    /// The default implementation copies the output of [`to_endian_bytes`]; the primitive
+    /// implementations write directly, without allocating.
+    ///
+    /// [`to_endian_bytes`]: EndianSerialize::to_endian_bytes
+    fn write_endian_bytes(self, endianness: &Endianness, out: &mut [u8])
+    where
+        Self: Sized,
+    {
+        out.copy_from_slice(&self.to_endian_bytes(endianness));
+    }
 }
 
 /// A trait for constructing numeric types from bytes, respecting endianness.
@@ -65,7 +101,7 @@ pub trait EndianSerialize {
 /// - `Number`: The target type produced by deserialization
 ///
 /// # Example
-/// ```
+/// ```ignore
 /// let bytes = [0x78, 0x56, 0x34, 0x12];
 /// let value = <u32 as EndianDeserialize>::from_endian_bytes(&bytes, Endianness::LittleEndian);
 /// ```
@@ -75,3 +111,319 @@ pub trait EndianDeserialize<'a> {
     type Number;
     fn from_endian_bytes(bytes: &'a [u8], endianness: Endianness) -> Self::Number;
 }
+
+/// A C-like enum stored as its integer discriminant.
+///
+/// Usually implemented through [`impl_repr_enum!`], which also implements the encoding and
+/// decoding traits on top of it.
+///
+/// [`impl_repr_enum!`]: crate::impl_repr_enum
+pub trait ReprEnum: Sized {
+    /// The integer type the discriminant is stored as.
+    type Repr;
+
+    /// Returns the discriminant of this variant.
+    fn to_repr(&self) -> Self::Repr;
+
+    /// Returns the variant for `repr`, or `None` if no variant has that discriminant.
+    fn from_repr(repr: Self::Repr) -> Option<Self>;
+}
+
+/// Implements [`ReprEnum`], [`SinkEncodable`] and [`SourceDecodable`] (and with them
+/// [`Encodable`], [`WriteEncodable`], [`Decodable`] and [`StreamDecodable`]) for a C-like enum, writing it as its `$repr` discriminant.
+///
+/// Each variant is listed with its discriminant. Decoding an unknown discriminant is an
+/// error, unless the list ends with `_ => Variant`, in which case the raw value is kept in
+/// that tuple variant and written back unchanged.
+///
+/// # Example
+/// ```
+/// use dataparser_core::{DataEncoder, DataParser, Decodable, impl_repr_enum};
+///
+/// #[derive(Debug, PartialEq)]
+/// #[repr(u8)]
+/// enum Color {
+///     Red = 1,
+///     Green = 2,
+/// }
+/// impl_repr_enum!(Color: u8 { Red = 1, Green = 2 });
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Opcode {
+///     Nop,
+///     Jump,
+///     Unknown(u16),
+/// }
+/// impl_repr_enum!(Opcode: u16 { Nop = 0, Jump = 1, _ => Unknown });
+///
+/// let mut encoder = DataEncoder::new();
+/// encoder.add_u8(2)?;
+/// encoder.add_u8(7)?;
+/// encoder.add_u16(0x99)?;
+/// let mut parser = DataParser::new(encoder.get_data()?);
+/// assert_eq!(Color::from_parser(&mut parser)?, Color::Green);
+/// assert!(Color::from_parser(&mut parser).is_err());
+/// assert_eq!(Opcode::from_parser(&mut parser)?, Opcode::Unknown(0x99));
+/// # Ok::<(), dataparser_core::DataParseError>(())
+/// ```
+///
+/// [`Encodable`]: crate::Encodable
+/// [`Decodable`]: crate::Decodable
+/// [`StreamDecodable`]: crate::StreamDecodable
+/// [`SourceDecodable`]: crate::SourceDecodable
+/// [`SinkEncodable`]: crate::SinkEncodable
+/// [`WriteEncodable`]: crate::encoder::writers::sync_writer::helpers::WriteEncodable
+#[macro_export]
+macro_rules! impl_repr_enum {
+    ($name:ident : $repr:ty { $($variant:ident = $value:expr),+ , _ => $unknown:ident $(,)? }) => {
+        impl $crate::utils::ReprEnum for $name {
+            type Repr = $repr;
+
+            fn to_repr(&self) -> $repr {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::$unknown(raw) => *raw,
+                }
+            }
+
+            fn from_repr(repr: $repr) -> Option<Self> {
+                $(
+                    if repr == $value {
+                        return Some($name::$variant);
+                    }
+                )+
+                Some($name::$unknown(repr))
+            }
+        }
+        $crate::impl_repr_enum!(@codec $name, $repr);
+    };
+    ($name:ident : $repr:ty { $($variant:ident = $value:expr),+ $(,)? }) => {
+        impl $crate::utils::ReprEnum for $name {
+            type Repr = $repr;
+
+            fn to_repr(&self) -> $repr {
+                match self {
+                    $($name::$variant => $value,)+
+                }
+            }
+
+            fn from_repr(repr: $repr) -> Option<Self> {
+                $(
+                    if repr == $value {
+                        return Some($name::$variant);
+                    }
+                )+
+                None
+            }
+        }
+        $crate::impl_repr_enum!(@codec $name, $repr);
+    };
+    (@codec $name:ident, $repr:ty) => {
+        $crate::__paste! {
+            impl $crate::SinkEncodable for $name {
+                fn encode_to<S: $crate::ByteSink>(&self, sink: &mut S) -> $crate::ParseResult<()> {
+                    sink.[<add_ $repr>]($crate::utils::ReprEnum::to_repr(self))
+                }
+            }
+
+            impl $crate::SourceDecodable for $name {
+                fn from_source<S: $crate::ByteSource>(source: &mut S) -> $crate::ParseResult<Self> {
+                    let raw = source.[<get_ $repr>]()?;
+                    <Self as $crate::utils::ReprEnum>::from_repr(raw).ok_or_else(|| {
+                        $crate::DataParseError::InvalidConversion {
+                            e: format!("Unknown {} tag {:?}", stringify!($name), raw),
+                        }
+                    })
+                }
+            }
+        }
+    };
+}
+
+/// Encodes an unsigned integer as unsigned LEB128.
+///
+/// Each byte holds 7 bits of the value (least significant group first), with the high bit
+/// set on every byte except the last.
+pub(crate) fn encode_uleb128(mut value: u128) -> Vec<u8> {
+    let mut out = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Encodes a signed integer as signed LEB128 (two's complement, sign-extended).
+pub(crate) fn encode_sleb128(mut value: i128) -> Vec<u8> {
+    let mut out = Vec::new();
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        let done = (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0);
+        if done {
+            out.push(byte);
+            return out;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Maps a signed integer onto an unsigned one so that small magnitudes stay small
+/// (`0 => 0, -1 => 1, 1 => 2, -2 => 3, ...`).
+pub(crate) fn zigzag_encode(value: i128) -> u128 {
+    ((value << 1) ^ (value >> 127)) as u128
+}
+
+/// Inverse of [`zigzag_encode`].
+pub(crate) fn zigzag_decode(value: u128) -> i128 {
+    ((value >> 1) as i128) ^ -((value & 1) as i128)
+}
+
+fn varint_overflow(bits: u32) -> DataParseError {
+    DataParseError::InvalidConversion {
+        e: format!("LEB128 value overflows a {}-bit integer", bits),
+    }
+}
+
+/// Decodes an unsigned LEB128 value that must fit in `bits` bits.
+///
+/// `next` is called once per byte. Encodings that are longer than the target width allows,
+/// or that carry set bits past it, are rejected.
+pub(crate) fn decode_uleb128<F>(bits: u32, mut next: F) -> ParseResult<u128>
+where
+    F: FnMut() -> ParseResult<u8>,
+{
+    let mut result = 0u128;
+    let mut shift = 0u32;
+    loop {
+        let byte = next()?;
+        let payload = u128::from(byte & 0x7f);
+        if shift >= bits || (bits - shift < 7 && payload >> (bits - shift) != 0) {
+            return Err(varint_overflow(bits));
+        }
+        result |= payload << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            return Ok(result);
+        }
+    }
+}
+
+/// Decodes a signed LEB128 value that must fit in `bits` bits.
+///
+/// See [`decode_uleb128`] for the overflow rules; for signed values the bits past the
+/// target width must be a sign extension.
+pub(crate) fn decode_sleb128<F>(bits: u32, mut next: F) -> ParseResult<i128>
+where
+    F: FnMut() -> ParseResult<u8>,
+{
+    let mut result = 0i128;
+    let mut shift = 0u32;
+    loop {
+        let byte = next()?;
+        let payload = i128::from(byte & 0x7f);
+        if shift >= bits {
+            return Err(varint_overflow(bits));
+        }
+        let available = bits - shift;
+        if available < 7 {
+            let high = payload >> (available - 1);
+            if (high != 0 && high != 0x7f >> (available - 1)) || byte & 0x80 != 0 {
+                return Err(varint_overflow(bits));
+            }
+        }
+        result |= payload << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            if shift < 128 && byte & 0x40 != 0 {
+                result |= -1i128 << shift;
+            }
+            return Ok(result);
+        }
+    }
+}
+
+/// Returns `true` if the given endianness resolves to big-endian on this machine.
+fn is_big_endian(endianness: &Endianness) -> bool {
+    match endianness {
+        Endianness::BigEndian => true,
+        Endianness::LittleEndian => false,
+        Endianness::NativeEndian => cfg!(target_endian = "big"),
+    }
+}
+
+pub(crate) fn check_uint_width(n_bytes: usize) -> ParseResult<()> {
+    if n_bytes == 0 || n_bytes > 8 {
+        return Err(DataParseError::InvalidConversion {
+            e: format!(
+                "Integer width must be between 1 and 8 bytes, got {}",
+                n_bytes
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Interprets `bytes` (1 to 8 of them) as an unsigned integer in the given byte order.
+pub(crate) fn uint_from_endian_bytes(bytes: &[u8], endianness: &Endianness) -> ParseResult<u64> {
+    check_uint_width(bytes.len())?;
+    let mut buf = [0u8; 8];
+    if is_big_endian(endianness) {
+        buf[8 - bytes.len()..].copy_from_slice(bytes);
+        Ok(u64::from_be_bytes(buf))
+    } else {
+        buf[..bytes.len()].copy_from_slice(bytes);
+        Ok(u64::from_le_bytes(buf))
+    }
+}
+
+/// Sign-extends the low `n_bytes` bytes of `value` into an `i64`.
+pub(crate) fn sign_extend(value: u64, n_bytes: usize) -> i64 {
+    let shift = 64 - 8 * n_bytes as u32;
+    ((value << shift) as i64) >> shift
+}
+
+/// Writes `value` as an `n_bytes`-wide unsigned integer in the given byte order.
+///
+/// # Errors
+/// Returns an error if `n_bytes` is not between 1 and 8, or `value` doesn't fit in `n_bytes` bytes.
+pub(crate) fn uint_to_endian_bytes(
+    value: u64,
+    n_bytes: usize,
+    endianness: &Endianness,
+) -> ParseResult<Vec<u8>> {
+    check_uint_width(n_bytes)?;
+    if n_bytes < 8 && value >> (8 * n_bytes) != 0 {
+        return Err(DataParseError::InvalidConversion {
+            e: format!("Value {} does not fit in {} bytes", value, n_bytes),
+        });
+    }
+    if is_big_endian(endianness) {
+        Ok(value.to_be_bytes()[8 - n_bytes..].to_vec())
+    } else {
+        Ok(value.to_le_bytes()[..n_bytes].to_vec())
+    }
+}
+
+/// Writes `value` as an `n_bytes`-wide two's complement integer in the given byte order.
+///
+/// # Errors
+/// Returns an error if `n_bytes` is not between 1 and 8, or `value` doesn't fit in `n_bytes` bytes.
+pub(crate) fn int_to_endian_bytes(
+    value: i64,
+    n_bytes: usize,
+    endianness: &Endianness,
+) -> ParseResult<Vec<u8>> {
+    check_uint_width(n_bytes)?;
+    let truncated = value as u64 & (u64::MAX >> (64 - 8 * n_bytes as u32));
+    if sign_extend(truncated, n_bytes) != value {
+        return Err(DataParseError::InvalidConversion {
+            e: format!("Value {} does not fit in {} bytes", value, n_bytes),
+        });
+    }
+    uint_to_endian_bytes(truncated, n_bytes, endianness)
+}
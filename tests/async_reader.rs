@@ -0,0 +1,35 @@
+//! `AsyncDataReader` decodes values that trickle in over a real async stream.
+#![cfg(feature = "async")]
+
+use dataparser_core::{
+    Encodable, EncodingOptions, parser::readers::async_reader::core::AsyncDataReader,
+};
+use tokio::io::AsyncWriteExt;
+
+#[test]
+fn decodes_values_fed_one_byte_at_a_time() {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let value = (
+            vec!["first".to_string(), String::new(), "third".to_string()],
+            0xBEEFu16,
+        );
+        let options = EncodingOptions::default();
+        let mut bytes = value.0.to_bytes(&options).unwrap();
+        bytes.extend(value.1.to_bytes(&options).unwrap());
+
+        // A one-byte pipe hands the reader every byte in its own read.
+        let (mut tx, rx) = tokio::io::duplex(1);
+        let writer = tokio::spawn(async move {
+            for byte in bytes {
+                tx.write_all(&[byte]).await.unwrap();
+                tokio::task::yield_now().await;
+            }
+        });
+
+        let mut reader = AsyncDataReader::new(rx);
+        assert_eq!(reader.decode::<Vec<String>>().await.unwrap(), value.0);
+        assert_eq!(reader.decode::<u16>().await.unwrap(), value.1);
+        writer.await.unwrap();
+        assert!(reader.decode::<u8>().await.unwrap_err().is_incomplete());
+    });
+}
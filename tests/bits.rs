@@ -0,0 +1,102 @@
+//! `BitWriter` and `BitParser` agree on the layout of bit fields in both bit orders.
+use dataparser_core::{DataEncoder, DataParser, utils::BitOrder};
+
+/// Fields of assorted widths, several of which straddle byte boundaries.
+const FIELDS: [(u64, u32); 9] = [
+    (0b1, 1),
+    (0b101, 3),
+    (0x3FF, 10),
+    (0, 2),
+    (0xAB_CDEF, 24),
+    (0x55, 7),
+    (u64::MAX, 64),
+    (0x1234_5678_9ABC, 48),
+    (0b10, 2),
+];
+
+#[test]
+fn fields_round_trip_in_each_order() {
+    for order in [BitOrder::MsbFirst, BitOrder::LsbFirst] {
+        let mut encoder = DataEncoder::new();
+        let mut bits = encoder.bits(order);
+        for (value, width) in FIELDS {
+            bits.write_bits(value, width).unwrap();
+        }
+        bits.finish().add_u8(0xEE).unwrap();
+        let total: u32 = FIELDS.iter().map(|(_, width)| width).sum();
+        let bytes = encoder.into_data();
+        assert_eq!(bytes.len(), total.div_ceil(8) as usize + 1);
+
+        let mut parser = DataParser::new(&bytes);
+        let mut bits = parser.bits(order);
+        for (value, width) in FIELDS {
+            assert_eq!(bits.read_bits(width).unwrap(), value, "{:?}", order);
+        }
+        assert_eq!(bits.finish().get_u8().unwrap(), 0xEE);
+    }
+}
+
+#[test]
+fn fields_spanning_a_byte_boundary() {
+    let cases = [
+        (BitOrder::MsbFirst, [0xB9, 0xA0]),
+        (BitOrder::LsbFirst, [0xD5, 0x0C]),
+    ];
+    for (order, expected) in cases {
+        let mut encoder = DataEncoder::new();
+        let mut bits = encoder.bits(order);
+        bits.write_bits(0b101, 3).unwrap();
+        bits.write_bits(0b1_1001_1010, 9).unwrap();
+        bits.finish();
+        assert_eq!(
+            encoder.get_data().unwrap(),
+            &expected.to_vec(),
+            "{:?}",
+            order
+        );
+
+        let mut parser = DataParser::new(&expected);
+        let mut bits = parser.bits(order);
+        assert_eq!(bits.read_bits(3).unwrap(), 0b101);
+        assert_eq!(bits.read_bits(9).unwrap(), 0b1_1001_1010);
+        assert_eq!(bits.read_bits(4).unwrap(), 0);
+    }
+}
+
+#[test]
+fn partial_byte_is_flushed_with_zero_padding() {
+    let cases = [
+        (BitOrder::MsbFirst, 0b1100_0000),
+        (BitOrder::LsbFirst, 0b0000_0011),
+    ];
+    for (order, flushed) in cases {
+        let mut encoder = DataEncoder::new();
+        let mut bits = encoder.bits(order);
+        bits.write_bits(0b11, 2).unwrap();
+        assert!(!bits.is_aligned());
+        bits.align();
+        assert!(bits.is_aligned());
+        bits.write_bit(true).unwrap();
+        bits.finish().add_u8(0x7F).unwrap();
+        let single = if order == BitOrder::MsbFirst {
+            0x80
+        } else {
+            0x01
+        };
+        assert_eq!(
+            encoder.get_data().unwrap(),
+            &vec![flushed, single, 0x7F],
+            "{:?}",
+            order
+        );
+
+        // Reading a partial byte and finishing skips the rest of it.
+        let bytes = encoder.into_data();
+        let mut parser = DataParser::new(&bytes);
+        let mut bits = parser.bits(order);
+        assert_eq!(bits.read_bits(2).unwrap(), 0b11);
+        bits.align();
+        assert!(bits.read_bit().unwrap());
+        assert_eq!(bits.finish().get_u8().unwrap(), 0x7F);
+    }
+}
@@ -0,0 +1,74 @@
+//! `DataCodec` frames values over a real async stream.
+#![cfg(feature = "async")]
+
+use dataparser_core::{DataParseError, codec::DataCodec};
+use futures::StreamExt;
+use tokio::io::AsyncWriteExt;
+use tokio_util::codec::FramedRead;
+
+#[test]
+fn frames_split_across_writes() {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (mut tx, rx) = tokio::io::duplex(64);
+        let mut frames = FramedRead::new(rx, DataCodec::<String>::new());
+
+        // Two frames, cut through the first prefix, the first payload and between the frames.
+        let chunks: [&[u8]; 4] = [
+            &[0x00, 0x00],
+            &[0x00, 0x06, 0x00, 0x00, 0x00, 0x02, b'h'],
+            &[b'i', 0x00, 0x00, 0x00, 0x05],
+            &[0x00, 0x00, 0x00, 0x01, b'!'],
+        ];
+        for chunk in chunks {
+            tx.write_all(chunk).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+        drop(tx);
+
+        assert_eq!(frames.next().await.unwrap().unwrap(), "hi");
+        assert_eq!(frames.next().await.unwrap().unwrap(), "!");
+        assert!(frames.next().await.is_none());
+    });
+}
+
+#[test]
+fn oversized_frame_is_rejected_before_its_payload_arrives() {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (mut tx, rx) = tokio::io::duplex(64);
+        let codec = DataCodec::<Vec<u8>>::new().with_max_frame_len(16);
+        let mut frames = FramedRead::new(rx, codec);
+        let capacity = frames.read_buffer().capacity();
+
+        // Only the prefix is sent and the stream stays open, so the error can't come from
+        // waiting for the payload.
+        tx.write_all(&[0x7f, 0xff, 0xff, 0xff]).await.unwrap();
+        let error = frames.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            error,
+            DataParseError::FrameTooLong {
+                len: 0x7fff_ffff,
+                max: 16
+            }
+        ));
+        assert!(frames.read_buffer().capacity() <= capacity);
+        drop(tx);
+    });
+}
+
+#[test]
+fn trailing_data_inside_a_frame_is_an_error() {
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let (mut tx, rx) = tokio::io::duplex(64);
+        let mut frames = FramedRead::new(rx, DataCodec::<u16>::new());
+
+        tx.write_all(&[0x00, 0x00, 0x00, 0x03, 0x01, 0x02, 0x03])
+            .await
+            .unwrap();
+        drop(tx);
+        let error = frames.next().await.unwrap().unwrap_err();
+        assert!(matches!(
+            error.kind(),
+            DataParseError::TrailingData { len: 1 }
+        ));
+    });
+}
@@ -0,0 +1,120 @@
+//! `Vec<T>` and `Option<T>` decode for element types that only implement `Decodable` or
+//! `StreamDecodable`, as hand-written impls and derived code rely on.
+use std::io::{Cursor, Read};
+
+use dataparser_core::{
+    DataParseError, DataParser, Decodable, ParseOptions, ParseResult, StreamDecodable,
+    parser::readers::sync_reader::core::DataReader,
+};
+
+#[derive(Debug, PartialEq)]
+struct Header {
+    id: u32,
+    flag: bool,
+}
+
+impl Decodable for Header {
+    fn from_parser(parser: &mut DataParser) -> ParseResult<Self> {
+        Ok(Header {
+            id: parser.get_u32()?,
+            flag: parser.get_bool()?,
+        })
+    }
+}
+
+impl StreamDecodable for Header {
+    fn from_stream_parser<R: Read>(parser: &mut DataReader<R>) -> ParseResult<Self> {
+        Ok(Header {
+            id: parser.get_u32()?,
+            flag: parser.get_bool()?,
+        })
+    }
+}
+
+/// Two headers, each preceded by its encoded length.
+const HEADERS: [u8; 22] = [
+    0x00, 0x00, 0x00, 0x02, //
+    0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x01, 0x01, //
+    0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x02, 0x00,
+];
+
+fn headers() -> Vec<Header> {
+    vec![Header { id: 1, flag: true }, Header { id: 2, flag: false }]
+}
+
+#[test]
+fn vec_of_decodable() {
+    let options = ParseOptions::default();
+    assert_eq!(
+        Vec::<Header>::from_bytes(&HEADERS, &options).unwrap(),
+        headers()
+    );
+
+    let mut parser = DataParser::new(&HEADERS);
+    assert_eq!(parser.get_vector::<Header>().unwrap(), headers());
+}
+
+#[test]
+fn option_of_decodable() {
+    let options = ParseOptions::default();
+    let some = [0x01, 0x00, 0x00, 0x00, 0x07, 0x01];
+    assert_eq!(
+        Option::<Header>::from_bytes(&some, &options).unwrap(),
+        Some(Header { id: 7, flag: true })
+    );
+    assert_eq!(
+        Option::<Header>::from_bytes(&[0x00], &options).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn nested_containers_of_decodable() {
+    let mut bytes = vec![0x01];
+    bytes.extend_from_slice(&HEADERS);
+    let decoded = Option::<Vec<Header>>::from_bytes(&bytes, &ParseOptions::default()).unwrap();
+    assert_eq!(decoded, Some(headers()));
+}
+
+#[test]
+fn borrowed_sections_are_not_counted_as_allocations() {
+    // `[[header, header]]`: an outer section holding two inner ones.
+    let mut bytes = vec![
+        0x00,
+        0x00,
+        0x00,
+        0x01,
+        0x00,
+        0x00,
+        0x00,
+        HEADERS.len() as u8,
+    ];
+    bytes.extend_from_slice(&HEADERS);
+    // Exactly what the decoded vectors take; copying any section would go over.
+    let max_alloc = size_of::<Vec<Header>>() + 2 * size_of::<Header>();
+    let options = ParseOptions::default().with_max_alloc(max_alloc);
+
+    let mut parser = DataParser::with_options(&bytes[..], options.clone());
+    assert_eq!(parser.get_vector::<Vec<Header>>().unwrap(), vec![headers()]);
+
+    let mut parser = DataParser::with_options(bytes.clone(), options);
+    assert!(matches!(
+        parser.get_vector::<Vec<Header>>().unwrap_err().kind(),
+        DataParseError::LimitExceeded { .. }
+    ));
+}
+
+#[test]
+fn vec_of_stream_decodable() {
+    let mut reader = DataReader::new(Cursor::new(HEADERS));
+    assert_eq!(
+        Vec::<Header>::from_stream_parser(&mut reader).unwrap(),
+        headers()
+    );
+
+    let mut reader = DataReader::new(Cursor::new([0x01, 0x00, 0x00, 0x00, 0x07, 0x01]));
+    assert_eq!(
+        Option::<Header>::from_stream_parser(&mut reader).unwrap(),
+        Some(Header { id: 7, flag: true })
+    );
+}
@@ -0,0 +1,250 @@
+//! `Vec<T>` and `Option<T>` encode to the same bytes through every encoder, for element types
+//! that only implement `Encodable` as well as `SinkEncodable` ones, and decode back.
+use std::fmt::Debug;
+
+use dataparser_core::{
+    ByteSink, ByteSource, DataEncoder, DataParser, Decodable, Encodable, EncodingOptions,
+    Endianness, ParseOptions, ParseResult, SinkEncodable, SourceDecodable,
+    encoder::{
+        counting::CountingSink,
+        fixed::FixedEncoder,
+        writers::sync_writer::{core::DataWriter, helpers::WriteEncodable},
+    },
+    options::LengthPrefix,
+};
+
+/// Implements only `Encodable` and `Decodable`, like hand-written and derived impls do.
+#[derive(Debug, Clone, PartialEq)]
+struct Header {
+    id: u32,
+    flag: bool,
+}
+
+impl Encodable for Header {
+    fn encode_data(&self, encoder: &mut DataEncoder) -> ParseResult<()> {
+        encoder.add_u32(self.id)?;
+        encoder.add_bool(self.flag)
+    }
+}
+
+impl Decodable for Header {
+    fn from_parser(parser: &mut DataParser) -> ParseResult<Self> {
+        Ok(Header {
+            id: parser.get_u32()?,
+            flag: parser.get_bool()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Record {
+    id: u16,
+    name: String,
+    tags: Vec<u16>,
+    parent: Option<u32>,
+}
+
+impl SinkEncodable for Record {
+    fn encode_to<S: ByteSink>(&self, sink: &mut S) -> ParseResult<()> {
+        sink.add_u16(self.id)?;
+        sink.add_string(self.name.as_str())?;
+        sink.add_slice(&self.tags)?;
+        sink.add_option(self.parent.as_ref())
+    }
+}
+
+impl SourceDecodable for Record {
+    fn from_source<S: ByteSource>(source: &mut S) -> ParseResult<Self> {
+        Ok(Record {
+            id: source.get_u16()?,
+            name: source.get_string(false)?,
+            tags: source.get_vector()?,
+            parent: source.get_option()?,
+        })
+    }
+}
+
+fn headers() -> Vec<Header> {
+    vec![
+        Header { id: 1, flag: true },
+        Header {
+            id: 0xdead_beef,
+            flag: false,
+        },
+    ]
+}
+
+fn records() -> Vec<Record> {
+    vec![
+        Record {
+            id: 7,
+            name: "first".into(),
+            tags: vec![1, 2, 3],
+            parent: None,
+        },
+        Record {
+            id: 300,
+            name: String::new(),
+            tags: Vec::new(),
+            parent: Some(7),
+        },
+    ]
+}
+
+/// The encoding options for each layout under test, with the parsing options that read it back.
+///
+/// The parser has no counterpart to `prepend_data_size` (it also size-prefixes strings and
+/// booleans, which `length_prefixed_fields` doesn't expect), so those layouts are only
+/// compared across encoders.
+fn configs() -> Vec<(EncodingOptions, Option<ParseOptions>)> {
+    let mut little = ParseOptions::default().with_length_prefix(LengthPrefix::Varint);
+    little.set_endianness(Endianness::LittleEndian);
+    vec![
+        (EncodingOptions::default(), Some(ParseOptions::default())),
+        (
+            EncodingOptions::default().with_endianness(Endianness::LittleEndian),
+            Some({
+                let mut options = ParseOptions::default();
+                options.set_endianness(Endianness::LittleEndian);
+                options
+            }),
+        ),
+        (
+            EncodingOptions::default().with_length_prefix(LengthPrefix::Varint),
+            Some(ParseOptions::default().with_length_prefix(LengthPrefix::Varint)),
+        ),
+        (
+            EncodingOptions::default()
+                .with_endianness(Endianness::LittleEndian)
+                .with_length_prefix(LengthPrefix::Varint),
+            Some(little),
+        ),
+        (EncodingOptions::default().with_prepended_data_size(), None),
+        (
+            EncodingOptions::default()
+                .with_endianness(Endianness::LittleEndian)
+                .with_prepended_data_size()
+                .with_length_prefix(LengthPrefix::Varint),
+            None,
+        ),
+    ]
+}
+
+/// Encodes `value` with every synchronous encoder, checks they agree, and returns the bytes.
+fn encode_everywhere<T: Encodable>(value: &T, options: &EncodingOptions) -> Vec<u8> {
+    let expected = value.to_bytes(options).unwrap();
+
+    let mut encoder = DataEncoder::new();
+    encoder.set_options(options);
+    value.encode_data(&mut encoder).unwrap();
+    assert_eq!(encoder.get_data().unwrap(), &expected);
+
+    let mut written = Vec::new();
+    DataWriter::with_options(&mut written, options.clone())
+        .encode(value)
+        .unwrap();
+    assert_eq!(written, expected);
+
+    let mut scratch = vec![0u8; expected.len()];
+    let mut fixed = FixedEncoder::with_options(&mut scratch[..], options.clone());
+    fixed.encode(value).unwrap();
+    assert_eq!(fixed.get_data(), &expected[..]);
+
+    assert_eq!(value.encoded_len(options).unwrap(), expected.len());
+    expected
+}
+
+fn round_trip<T: Encodable + Decodable + PartialEq + Debug>(value: &T) {
+    for (encoding, parsing) in configs() {
+        let bytes = encode_everywhere(value, &encoding);
+        if let Some(parsing) = parsing {
+            assert_eq!(&T::from_bytes(&bytes, &parsing).unwrap(), value);
+        }
+    }
+}
+
+#[test]
+fn encodable_only_containers() {
+    round_trip(&headers());
+    round_trip(&Some(headers()[1].clone()));
+    round_trip(&Option::<Header>::None);
+    round_trip(&vec![Some(headers()[0].clone()), None]);
+}
+
+#[test]
+fn sink_encodable_containers() {
+    round_trip(&records());
+    round_trip(&Some(records()[0].clone()));
+    round_trip(&vec![records(), Vec::new()]);
+}
+
+#[test]
+fn sinks_write_slices_like_encode() {
+    for (encoding, _) in configs() {
+        let expected = records().to_bytes(&encoding).unwrap();
+
+        let mut sink = CountingSink::new(encoding.clone());
+        sink.add_slice(&records()).unwrap();
+        assert_eq!(sink.len(), expected.len());
+
+        let mut written = Vec::new();
+        let mut writer = DataWriter::with_options(&mut written, encoding.clone());
+        records().to_writer(&mut writer).unwrap();
+        assert_eq!(written, expected);
+
+        let mut written = Vec::new();
+        let mut writer = DataWriter::with_options(&mut written, encoding.clone());
+        writer.encode(&headers()).unwrap();
+        assert_eq!(written, headers().to_bytes(&encoding).unwrap());
+    }
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn async_writer_matches() {
+    use dataparser_core::encoder::writers::async_writer::{
+        core::AsyncDataWriter, helpers::AsyncEncodable,
+    };
+
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        for (encoding, parsing) in configs() {
+            let mut written = Vec::new();
+            let mut writer = AsyncDataWriter::with_options(&mut written, encoding.clone());
+            writer.encode(&headers()).await.unwrap();
+            writer.encode(&Some(records())).await.unwrap();
+            drop(writer);
+            let mut expected = encode_everywhere(&headers(), &encoding);
+            expected.extend(encode_everywhere(&Some(records()), &encoding));
+            assert_eq!(written, expected);
+            if let Some(parsing) = parsing {
+                let (decoded, rest) = Vec::<Header>::decode_prefix(&written, &parsing).unwrap();
+                assert_eq!(decoded, headers());
+                assert_eq!(
+                    Option::<Vec<Record>>::from_bytes(rest, &parsing).unwrap(),
+                    Some(records())
+                );
+            }
+
+            // `Record` is only `SinkEncodable`, so this goes through the blanket
+            // `AsyncEncodable` impl for its elements.
+            let mut written = Vec::new();
+            let mut writer = AsyncDataWriter::with_options(&mut written, encoding.clone());
+            records().async_to_writer(&mut writer).await.unwrap();
+            records()[1].async_to_writer(&mut writer).await.unwrap();
+            drop(writer);
+            let mut expected = encode_everywhere(&records(), &encoding);
+            expected.extend(encode_everywhere(&records()[1], &encoding));
+            assert_eq!(written, expected);
+
+            let value = (vec!["a".to_string(), "bc".to_string()], Some(-2i64));
+            let mut written = Vec::new();
+            let mut writer = AsyncDataWriter::with_options(&mut written, encoding.clone());
+            value.0.async_to_writer(&mut writer).await.unwrap();
+            value.1.async_to_writer(&mut writer).await.unwrap();
+            drop(writer);
+            let mut expected = encode_everywhere(&value.0, &encoding);
+            expected.extend(encode_everywhere(&value.1, &encoding));
+            assert_eq!(written, expected);
+        }
+    });
+}
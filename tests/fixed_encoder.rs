@@ -0,0 +1,58 @@
+//! `FixedEncoder` refuses values that don't fit without touching the caller's buffer.
+use dataparser_core::{
+    DataParseError, Encodable, EncodingOptions, encoder::fixed::FixedEncoder, options::LengthPrefix,
+};
+
+fn assert_one_byte_short<T: Encodable>(value: &T, options: &EncodingOptions) {
+    let expected = value.to_bytes(options).unwrap();
+    let len = expected.len();
+    // One byte of guard on each side of the slice handed to the encoder.
+    let mut backing = vec![0xA5; len + 1];
+    {
+        let mut encoder = FixedEncoder::with_options(&mut backing[1..len], options.clone());
+        match encoder.encode(value) {
+            Err(DataParseError::BufferFull { required, capacity }) => {
+                assert_eq!((required, capacity), (len, len - 1));
+            }
+            other => panic!("expected BufferFull, got {:?}", other),
+        }
+        assert_eq!(encoder.position(), 0);
+    }
+    assert!(backing.iter().all(|&b| b == 0xA5));
+
+    let mut encoder = FixedEncoder::with_options(&mut backing[1..], options.clone());
+    encoder.encode(value).unwrap();
+    assert_eq!(encoder.get_data(), &expected[..]);
+    assert_eq!(backing[0], 0xA5);
+}
+
+#[test]
+fn buffer_one_byte_too_short() {
+    let options = [
+        EncodingOptions::default(),
+        EncodingOptions::default()
+            .with_prepended_data_size()
+            .with_length_prefix(LengthPrefix::Varint),
+    ];
+    for options in &options {
+        assert_one_byte_short(&0xDEAD_BEEFu32, options);
+        assert_one_byte_short(&"hello".to_string(), options);
+        assert_one_byte_short(&vec![Some(1u16), None, Some(3)], options);
+        assert_one_byte_short(&[vec!["a".to_string()], Vec::new()], options);
+    }
+}
+
+#[test]
+fn second_value_one_byte_too_short() {
+    let mut scratch = [0u8; 7];
+    let mut encoder = FixedEncoder::new(&mut scratch[..]);
+    encoder.encode(&1u16).unwrap();
+    match encoder.encode(&"abc".to_string()) {
+        Err(DataParseError::BufferFull { required, capacity }) => {
+            assert_eq!((required, capacity), (9, 7));
+        }
+        other => panic!("expected BufferFull, got {:?}", other),
+    }
+    assert_eq!(encoder.position(), 2);
+    assert_eq!(scratch, [0x00, 0x01, 0, 0, 0, 0, 0]);
+}
@@ -0,0 +1,88 @@
+//! LEB128 and zigzag varints round-trip at the edges of their range, and malformed or
+//! truncated encodings are rejected.
+use std::io::Cursor;
+
+use dataparser_core::{
+    DataEncoder, DataParseError, DataParser, parser::readers::sync_reader::core::DataReader,
+};
+
+#[test]
+fn extremes_round_trip() {
+    let mut encoder = DataEncoder::new();
+    encoder.add_leb128_u64(u64::MAX).unwrap();
+    encoder.add_leb128_i64(i64::MIN).unwrap();
+    encoder.add_leb128_i64(i64::MAX).unwrap();
+    encoder.add_zigzag_i64(i64::MIN).unwrap();
+    let bytes = encoder.into_data();
+    assert_eq!(
+        bytes[..10],
+        [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]
+    );
+    assert_eq!(
+        bytes[10..20],
+        [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7F]
+    );
+
+    let mut parser = DataParser::new(&bytes);
+    assert_eq!(parser.get_leb128_u64().unwrap(), u64::MAX);
+    assert_eq!(parser.get_leb128_i64().unwrap(), i64::MIN);
+    assert_eq!(parser.get_leb128_i64().unwrap(), i64::MAX);
+    assert_eq!(parser.get_zigzag_i64().unwrap(), i64::MIN);
+    assert_eq!(parser.remaining(), 0);
+}
+
+#[test]
+fn over_long_encoding_is_rejected() {
+    // Zero, padded out to 11 bytes with continuation bits.
+    let mut bytes = vec![0x80; 10];
+    bytes.push(0x00);
+    assert!(matches!(
+        DataParser::new(&bytes).get_leb128_u64().unwrap_err().kind(),
+        DataParseError::InvalidConversion { .. }
+    ));
+    assert!(matches!(
+        DataParser::new(&bytes).get_leb128_i64().unwrap_err().kind(),
+        DataParseError::InvalidConversion { .. }
+    ));
+}
+
+#[test]
+fn overflow_in_last_byte_is_rejected() {
+    // The 10th byte of a 64-bit value only has room for one more bit.
+    let mut unsigned = vec![0xFF; 9];
+    unsigned.push(0x02);
+    assert!(matches!(
+        DataParser::new(&unsigned)
+            .get_leb128_u64()
+            .unwrap_err()
+            .kind(),
+        DataParseError::InvalidConversion { .. }
+    ));
+
+    // For signed values, that bit must be sign-extended into the rest of the byte.
+    let mut signed = vec![0x80; 9];
+    signed.push(0x02);
+    assert!(matches!(
+        DataParser::new(&signed)
+            .get_leb128_i64()
+            .unwrap_err()
+            .kind(),
+        DataParseError::InvalidConversion { .. }
+    ));
+}
+
+#[test]
+fn truncated_varint_is_incomplete() {
+    let bytes = [0xFF, 0xFF];
+    let error = DataParser::new(&bytes).get_leb128_u64().unwrap_err();
+    assert!(matches!(error.kind(), DataParseError::Incomplete { .. }));
+    assert_eq!(error.missing_bytes(), Some(1));
+
+    let error = DataParser::new(&bytes).get_zigzag_i32().unwrap_err();
+    assert!(error.is_incomplete());
+
+    let error = DataReader::new(Cursor::new(bytes))
+        .get_leb128_u64()
+        .unwrap_err();
+    assert!(error.is_incomplete());
+}
@@ -0,0 +1,215 @@
+//! Every limit of `ParseOptions` is enforced by both `DataParser` and `DataReader`, and a
+//! hostile length prefix is rejected before anything is allocated for it.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::Cursor;
+
+use dataparser_core::{
+    DataParseError, DataParser, FrameDecoder, ParseOptions, ParseResult, options::Limit,
+    parser::readers::sync_reader::core::DataReader,
+};
+
+/// Records the largest allocation made by the current thread.
+struct TrackingAllocator;
+
+thread_local! {
+    static LARGEST: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for TrackingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = LARGEST.try_with(|largest| largest.set(largest.get().max(layout.size())));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = LARGEST.try_with(|largest| largest.set(largest.get().max(new_size)));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: TrackingAllocator = TrackingAllocator;
+
+/// Runs `f` and returns its result along with the largest allocation it made.
+fn largest_allocation<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    LARGEST.with(|largest| largest.set(0));
+    let result = f();
+    (result, LARGEST.with(Cell::get))
+}
+
+fn assert_limit<T: std::fmt::Debug>(result: ParseResult<T>, expected: Limit) {
+    match result.unwrap_err().kind() {
+        DataParseError::LimitExceeded { limit, .. } => assert_eq!(*limit, expected),
+        other => panic!("expected a {} limit error, got {:?}", expected, other),
+    }
+}
+
+/// Three `u8`s in a vector.
+const VECTOR: [u8; 19] = [
+    0x00, 0x00, 0x00, 0x03, //
+    0x00, 0x00, 0x00, 0x01, 0x0A, //
+    0x00, 0x00, 0x00, 0x01, 0x0B, //
+    0x00, 0x00, 0x00, 0x01, 0x0C,
+];
+
+/// The string `"hello"`.
+const STRING: [u8; 9] = [0x00, 0x00, 0x00, 0x05, b'h', b'e', b'l', b'l', b'o'];
+
+/// `Some(Some(7u8))`.
+const NESTED: [u8; 3] = [0x01, 0x01, 0x07];
+
+/// A length prefix claiming 4 GiB of data, followed by almost nothing.
+const HOSTILE: [u8; 6] = [0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00];
+
+fn parser(data: &[u8], options: ParseOptions) -> DataParser<'_> {
+    DataParser::with_options(data, options)
+}
+
+fn reader(data: &[u8], options: ParseOptions) -> DataReader<Cursor<Vec<u8>>> {
+    DataReader::with_options(Cursor::new(data.to_vec()), options)
+}
+
+#[test]
+fn max_collection_len() {
+    let options = ParseOptions::default().with_max_collection_len(2);
+    assert_limit(
+        parser(&VECTOR, options.clone()).get_vector::<u8>(),
+        Limit::CollectionLength,
+    );
+    assert_limit(
+        reader(&VECTOR, options.clone()).get_vector::<u8>(),
+        Limit::CollectionLength,
+    );
+
+    let options = ParseOptions::default().with_max_collection_len(3);
+    assert_eq!(
+        parser(&VECTOR, options.clone()).get_vector::<u8>().unwrap(),
+        [10, 11, 12]
+    );
+    assert_eq!(
+        reader(&VECTOR, options).get_vector::<u8>().unwrap(),
+        [10, 11, 12]
+    );
+}
+
+#[test]
+fn max_string_len() {
+    let options = ParseOptions::default().with_max_string_len(4);
+    assert_limit(
+        parser(&STRING, options.clone()).get_string(false),
+        Limit::StringLength,
+    );
+    assert_limit(
+        reader(&STRING, options).get_string(false),
+        Limit::StringLength,
+    );
+
+    let options = ParseOptions::default().with_max_string_len(5);
+    assert_eq!(
+        parser(&STRING, options.clone()).get_string(false).unwrap(),
+        "hello"
+    );
+    assert_eq!(reader(&STRING, options).get_string(false).unwrap(), "hello");
+}
+
+#[test]
+fn max_depth() {
+    let options = ParseOptions::default().with_max_depth(1);
+    assert_limit(
+        parser(&NESTED, options.clone()).get_option::<Option<u8>>(),
+        Limit::Depth,
+    );
+    assert_limit(
+        reader(&NESTED, options).get_option::<Option<u8>>(),
+        Limit::Depth,
+    );
+
+    let options = ParseOptions::default().with_max_depth(2);
+    assert_eq!(
+        parser(&NESTED, options.clone())
+            .get_option::<Option<u8>>()
+            .unwrap(),
+        Some(Some(7))
+    );
+    assert_eq!(
+        reader(&NESTED, options).get_option::<Option<u8>>().unwrap(),
+        Some(Some(7))
+    );
+}
+
+#[test]
+fn max_alloc() {
+    // The decoded vector alone takes 3 bytes.
+    let options = ParseOptions::default().with_max_alloc(2);
+    assert_limit(
+        parser(&VECTOR, options.clone()).get_vector::<u8>(),
+        Limit::Allocation,
+    );
+    assert_limit(
+        reader(&VECTOR, options).get_vector::<u8>(),
+        Limit::Allocation,
+    );
+
+    let options = ParseOptions::default().with_max_alloc(4);
+    assert_limit(
+        parser(&STRING, options.clone()).get_string(false),
+        Limit::Allocation,
+    );
+    assert_limit(
+        reader(&STRING, options).get_string(false),
+        Limit::Allocation,
+    );
+}
+
+#[test]
+fn max_buffered() {
+    // Each item of the vector is a 1-byte section; the whole vector is 19 bytes.
+    let options = ParseOptions::default().with_max_buffered(18);
+    let mut decoder = FrameDecoder::<Vec<u8>>::with_options(options.clone());
+    decoder.feed(&VECTOR[..18]);
+    assert_limit(decoder.next_frame(), Limit::Buffered);
+    assert_eq!(
+        parser(&VECTOR, options.clone()).get_vector::<u8>().unwrap(),
+        [10, 11, 12]
+    );
+
+    // A reader buffers each section before decoding it.
+    let options = ParseOptions::default().with_max_buffered(0);
+    assert_limit(reader(&VECTOR, options).get_vector::<u8>(), Limit::Buffered);
+    let options = ParseOptions::default().with_max_buffered(1);
+    assert_eq!(
+        reader(&VECTOR, options).get_vector::<u8>().unwrap(),
+        [10, 11, 12]
+    );
+}
+
+#[test]
+fn hostile_length_prefix() {
+    let options = ParseOptions::default().with_max_alloc(1024);
+
+    let (result, largest) =
+        largest_allocation(|| parser(&HOSTILE, options.clone()).get_string(false));
+    assert_limit(result, Limit::Allocation);
+    assert!(largest < 4096, "allocated {} bytes", largest);
+
+    let (result, largest) =
+        largest_allocation(|| reader(&HOSTILE, options.clone()).get_string(false));
+    assert_limit(result, Limit::Allocation);
+    assert!(largest < 4096, "allocated {} bytes", largest);
+
+    let (result, largest) =
+        largest_allocation(|| reader(&HOSTILE, options.clone()).get_vector::<Vec<u64>>());
+    assert_limit(result, Limit::Allocation);
+    assert!(largest < 4096, "allocated {} bytes", largest);
+
+    let options = options.with_max_collection_len(1024);
+    let (result, largest) =
+        largest_allocation(|| parser(&HOSTILE, options.clone()).get_vector::<u64>());
+    assert_limit(result, Limit::CollectionLength);
+    assert!(largest < 4096, "allocated {} bytes", largest);
+}
@@ -0,0 +1,89 @@
+//! Reserved fields and `length_prefixed` scopes are filled in place, and stale placeholders
+//! are refused instead of overwriting unrelated bytes.
+use dataparser_core::{ByteSink, DataEncoder, DataParseError, DataParser};
+
+#[test]
+fn length_too_wide_for_the_reserved_prefix() {
+    let mut encoder = DataEncoder::new();
+    encoder
+        .length_prefixed::<u8>(|enc| enc.add_item(&[0xAA; 255]))
+        .unwrap();
+    assert_eq!(encoder.get_data().unwrap()[0], 0xFF);
+    assert_eq!(encoder.get_data().unwrap().len(), 256);
+
+    // One byte too many for a `u8` prefix: the error leaves the buffer as it was.
+    assert!(
+        encoder
+            .length_prefixed::<u8>(|enc| enc.add_item(&[0xBB; 256]))
+            .is_err()
+    );
+    assert_eq!(encoder.get_data().unwrap().len(), 256);
+}
+
+#[test]
+fn nested_length_prefixed_scopes() {
+    let mut encoder = DataEncoder::new();
+    encoder
+        .length_prefixed::<u16>(|enc| {
+            enc.add_u8(1)?;
+            enc.length_prefixed::<u8>(|enc| enc.add_u32(0x0203_0405))?;
+            // A failed inner scope only removes what it wrote.
+            assert!(
+                enc.length_prefixed::<u8>(|enc| {
+                    enc.add_u16(0xFFFF)?;
+                    Err(DataParseError::Custom { e: "abort".into() })
+                })
+                .is_err()
+            );
+            enc.add_u8(6)
+        })
+        .unwrap();
+    assert_eq!(
+        encoder.get_data().unwrap(),
+        &vec![0x00, 0x07, 0x01, 0x04, 0x02, 0x03, 0x04, 0x05, 0x06]
+    );
+
+    let bytes = encoder.into_data();
+    let mut parser = DataParser::new(&bytes);
+    assert_eq!(parser.get_u16().unwrap(), 7);
+    assert_eq!(parser.get_u8().unwrap(), 1);
+    assert_eq!(parser.get_u8().unwrap(), 4);
+    assert_eq!(parser.get_u32().unwrap(), 0x0203_0405);
+    assert_eq!(parser.get_u8().unwrap(), 6);
+}
+
+#[test]
+fn placeholder_from_another_encoder_is_refused() {
+    let mut first = DataEncoder::new();
+    let placeholder = first.reserve_u16().unwrap();
+    let mut second = first.clone();
+    assert!(second.fill(placeholder.clone(), 7).is_err());
+    assert_eq!(second.get_data().unwrap(), &vec![0x00, 0x00]);
+    first.fill(placeholder, 7).unwrap();
+    assert_eq!(first.get_data().unwrap(), &vec![0x00, 0x07]);
+}
+
+#[test]
+fn rolled_back_placeholder_is_refused() {
+    let mut encoder = DataEncoder::new();
+    let count = encoder.reserve_u8().unwrap();
+    let mut inner = None;
+    assert!(
+        encoder
+            .length_prefixed::<u8>(|enc| {
+                inner = Some(enc.reserve_u16()?);
+                Err(DataParseError::Custom { e: "abort".into() })
+            })
+            .is_err()
+    );
+    // New bytes now sit where the rolled-back field was.
+    encoder.add_u32(0x0A0B_0C0D).unwrap();
+    assert!(encoder.fill(inner.unwrap(), 0xFFFF).is_err());
+
+    // Fields reserved before the failed scope are unaffected.
+    encoder.fill(count, 1).unwrap();
+    assert_eq!(
+        encoder.get_data().unwrap(),
+        &vec![0x01, 0x0A, 0x0B, 0x0C, 0x0D]
+    );
+}